@@ -0,0 +1,1233 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::cell::RefCell;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use crate::board::Board;
+use crate::coordinates::{Coord, Coordinates};
+use crate::game::Game;
+use crate::outcome::Outcome;
+use crate::player::Player;
+use crate::state::State;
+
+const WIN: i32 = 1_000_000_000;
+const INFINITY: i32 = i32::MAX;
+const MEDIUM_DEPTH: u8 = 2;
+const HINT_BUDGET: Duration = Duration::from_millis(500);
+const HARD_BUDGET: Duration = Duration::from_secs(2);
+const PIECE_LIMIT_DEPTH: u8 = 6;
+
+// Tic-tac-toe's first two plies, as played by the search. Each entry lists the
+// moves played so far, from the first player, and the reply.
+type Opening = (&'static [(Coord, Coord)], (Coord, Coord));
+
+const OPENINGS: [Opening; 4] = [
+    (&[], (-1, -1)),
+    (&[(0, 0)], (-1, -1)),
+    (&[(-1, -1)], (0, 0)),
+    (&[(0, -1)], (-1, -1)),
+];
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+/// What a search went through to find its move.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct SearchStats {
+    pub nodes: u64,
+    pub elapsed: Duration,
+    pub depth: u8,
+}
+
+impl Difficulty {
+    pub fn next_move(
+        &self,
+        game: &Game,
+        board: &Board,
+        player: &Player,
+        rng: &mut impl Rng,
+    ) -> Option<Coordinates> {
+        self.next_move_with_stats(game, board, player, rng).0
+    }
+
+    // Moves that aren't searched for, random, from the book or the solved table,
+    // have no stats.
+    pub fn next_move_with_stats(
+        &self,
+        game: &Game,
+        board: &Board,
+        player: &Player,
+        rng: &mut impl Rng,
+    ) -> (Option<Coordinates>, Option<SearchStats>) {
+        let searched = |search: Search| {
+            let (best, stats) = search.best_move_with_stats(board, player);
+            (best, Some(stats))
+        };
+        match self {
+            Difficulty::Easy => (random_move(board, rng), None),
+            Difficulty::Medium => searched(Search {
+                max_depth: Some(MEDIUM_DEPTH),
+                ..Search::new(game)
+            }),
+            Difficulty::Hard => {
+                match opening(game, board, player).or_else(|| solved_move(board, player)) {
+                    Some(coordinates) => (Some(coordinates), None),
+                    // Past the book and the solved table a full search may not end.
+                    None => (best_move_timed(game, board, player, HARD_BUDGET), None),
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "easy" => Ok(Difficulty::Easy),
+            "medium" => Ok(Difficulty::Medium),
+            "hard" => Ok(Difficulty::Hard),
+            _ => Err("Difficulty can't be parsed".to_string()),
+        }
+    }
+}
+
+fn moves(board: &Board, player: &Player) -> Vec<(Coordinates, Board)> {
+    board
+        .available_moves()
+        .into_iter()
+        .filter_map(|coordinates| {
+            board
+                .insert(&coordinates, player)
+                .ok()
+                .map(|board| (coordinates, board))
+        })
+        .collect()
+}
+
+pub trait Strategy {
+    fn choose(&self, game: &Game, board: &Board, player: &Player) -> Coordinates;
+}
+
+pub struct Perfect;
+
+impl Strategy for Perfect {
+    fn choose(&self, game: &Game, board: &Board, player: &Player) -> Coordinates {
+        best_move(game, board, player).expect("Board is full")
+    }
+}
+
+pub struct DepthLimited(pub u8);
+
+impl Strategy for DepthLimited {
+    fn choose(&self, game: &Game, board: &Board, player: &Player) -> Coordinates {
+        best_move_with_depth(game, board, player, self.0).expect("Board is full")
+    }
+}
+
+pub struct Random<R: Rng> {
+    rng: RefCell<R>,
+}
+
+impl<R: Rng> Random<R> {
+    pub fn new(rng: R) -> Random<R> {
+        Random {
+            rng: RefCell::new(rng),
+        }
+    }
+}
+
+impl<R: Rng> Strategy for Random<R> {
+    fn choose(&self, _game: &Game, board: &Board, _player: &Player) -> Coordinates {
+        random_move(board, &mut *self.rng.borrow_mut()).expect("Board is full")
+    }
+}
+
+// How a cached score relates to the position's true score. Pruned searches only
+// bound it.
+#[derive(Clone, Copy)]
+enum Bound {
+    Exact,
+    Lower,
+    Upper,
+}
+
+struct Search<'a> {
+    game: &'a Game,
+    pruning: bool,
+    max_depth: Option<u8>,
+    deadline: Option<Instant>,
+    timed_out: bool,
+    nodes: u64,
+    deepest: u8,
+    table: Option<HashMap<(Board, Player), (i32, Bound)>>,
+}
+
+impl<'a> Search<'a> {
+    // With a piece limit boards never fill up, so the search needs a horizon. The
+    // oldest piece depends on the history, which cached positions don't keep.
+    fn new(game: &'a Game) -> Search<'a> {
+        let limited = game.max_pieces_per_player.is_some();
+        Search {
+            game,
+            pruning: true,
+            max_depth: if limited {
+                Some(PIECE_LIMIT_DEPTH)
+            } else {
+                None
+            },
+            deadline: None,
+            timed_out: false,
+            nodes: 0,
+            deepest: 0,
+            table: if limited { None } else { Some(HashMap::new()) },
+        }
+    }
+
+    // Symmetric positions share their scores. Gravity boards can't be turned
+    // around, so they are only cached as they are. A position is always reached
+    // at the same depth within a search, so depth adjusted scores can be reused.
+    fn key(&self, board: &Board, player: &Player) -> (Board, Player) {
+        if self.game.gravity {
+            (board.clone(), player.clone())
+        } else {
+            (board.canonical(), player.clone())
+        }
+    }
+
+    // Score of `player` having just played `coordinates`, from `player`'s point of view.
+    // Quicker wins and slower losses score higher. In misère, a completed line is a loss.
+    fn score(
+        &mut self,
+        board: &Board,
+        coordinates: &Coordinates,
+        player: &Player,
+        depth: i32,
+        alpha: i32,
+        beta: i32,
+    ) -> i32 {
+        self.deepest = self.deepest.max(u8::try_from(depth + 1).unwrap_or(u8::MAX));
+
+        if board.is_winning_move(coordinates, self.game.goal) && self.game.misere {
+            depth - WIN
+        } else if board.is_winning_move(coordinates, self.game.goal) {
+            WIN - depth
+        } else if board.is_draw() {
+            0
+        } else {
+            -self.minimax(board, &player.next(), depth + 1, -beta, -alpha)
+        }
+    }
+
+    fn minimax(
+        &mut self,
+        board: &Board,
+        player: &Player,
+        depth: i32,
+        alpha: i32,
+        beta: i32,
+    ) -> i32 {
+        self.nodes += 1;
+
+        // Past the deadline, scores are meaningless and the whole search is dropped.
+        if self.timed_out
+            || self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            self.timed_out = true;
+            return 0;
+        }
+
+        if self
+            .max_depth
+            .is_some_and(|max_depth| depth >= i32::from(max_depth))
+        {
+            return evaluate(self.game, board, player);
+        }
+
+        let key = self.table.as_ref().map(|_| self.key(board, player));
+        if let (Some(table), Some(key)) = (&self.table, &key) {
+            match table.get(key) {
+                Some((score, Bound::Exact)) => return *score,
+                Some((score, Bound::Lower)) if *score >= beta => return *score,
+                Some((score, Bound::Upper)) if *score <= alpha => return *score,
+                _ => (),
+            }
+        }
+
+        let original_alpha = alpha;
+        let mut alpha = alpha;
+        let mut best = -INFINITY;
+        for (coordinates, board) in moves(board, player) {
+            let score = self.score(&board, &coordinates, player, depth, alpha, beta);
+            best = best.max(score);
+            alpha = alpha.max(score);
+
+            if self.pruning && alpha >= beta {
+                break;
+            }
+        }
+
+        if let (Some(table), Some(key), false) = (self.table.as_mut(), key, self.timed_out) {
+            let bound = if best <= original_alpha {
+                Bound::Upper
+            } else if best >= beta {
+                Bound::Lower
+            } else {
+                Bound::Exact
+            };
+            table.insert(key, (best, bound));
+        }
+
+        best
+    }
+
+    fn best_move(&mut self, board: &Board, player: &Player) -> Option<Coordinates> {
+        let mut best = None;
+        let mut alpha = -INFINITY;
+        for (coordinates, board) in moves(board, player) {
+            let score = self.score(&board, &coordinates, player, 0, alpha, INFINITY);
+            if best.is_none() || score > alpha {
+                alpha = score;
+                best = Some(coordinates);
+            }
+        }
+
+        best
+    }
+
+    fn best_move_with_stats(
+        mut self,
+        board: &Board,
+        player: &Player,
+    ) -> (Option<Coordinates>, SearchStats) {
+        let start = Instant::now();
+        let best = self.best_move(board, player);
+        let stats = SearchStats {
+            nodes: self.nodes,
+            elapsed: start.elapsed(),
+            depth: self.deepest,
+        };
+
+        (best, stats)
+    }
+}
+
+// Sums every window of `game.goal` cells that only one player occupies, rewarding longer runs.
+// Windows with a blocked cell can't be completed and are left out.
+// Positive scores favour `player`, negative ones their opponent, the other way round in misère.
+pub fn evaluate(game: &Game, board: &Board, player: &Player) -> i32 {
+    let sign = if game.misere { -1 } else { 1 };
+    sign * board
+        .lines_with_direction()
+        .iter()
+        .flat_map(|(direction, line)| line.windows(game.goal_along(*direction).max(1) as usize))
+        .filter(|window| {
+            !window
+                .iter()
+                .any(|coordinates| board.is_blocked(coordinates))
+        })
+        .map(|window| {
+            let players = window
+                .iter()
+                .filter_map(|coordinates| board.get(coordinates))
+                .collect::<Vec<&Player>>();
+            let own = players.iter().filter(|p| **p == player).count() as u32;
+            let other = players.len() as u32 - own;
+
+            match (own, other) {
+                (0, 0) => 0,
+                (own, 0) => 10_i32.pow(own - 1),
+                (0, other) => -10_i32.pow(other - 1),
+                _ => 0,
+            }
+        })
+        .sum::<i32>()
+}
+
+/// Picks uniformly among the empty cells, returning `None` when the board is full.
+pub fn random_move(board: &Board, rng: &mut impl Rng) -> Option<Coordinates> {
+    board.available_moves().choose(rng).cloned()
+}
+
+pub fn best_move(game: &Game, board: &Board, player: &Player) -> Option<Coordinates> {
+    Search::new(game).best_move(board, player)
+}
+
+pub fn best_move_with_stats(
+    game: &Game,
+    board: &Board,
+    player: &Player,
+) -> (Option<Coordinates>, SearchStats) {
+    Search::new(game).best_move_with_stats(board, player)
+}
+
+pub fn best_move_with_depth(
+    game: &Game,
+    board: &Board,
+    player: &Player,
+    depth: u8,
+) -> Option<Coordinates> {
+    Search {
+        max_depth: Some(depth),
+        ..Search::new(game)
+    }
+    .best_move(board, player)
+}
+
+// The book reply to a tic-tac-toe opening, in any of its orientations. Other
+// games and positions aren't in the book.
+pub fn opening(game: &Game, board: &Board, player: &Player) -> Option<Coordinates> {
+    let tic_tac_toe = Game {
+        symbols: Game::TIC_TAC_TOE.symbols,
+        ..*game
+    };
+    if tic_tac_toe != Game::TIC_TAC_TOE {
+        return None;
+    }
+
+    let canonical = board.canonical();
+    OPENINGS.iter().find_map(|(played, (x, y))| {
+        let first = if played.len() % 2 == 0 {
+            player.clone()
+        } else {
+            player.next()
+        };
+        let (_, position) =
+            played
+                .iter()
+                .try_fold((first, Board::new(game)), |(player, position), (x, y)| {
+                    let position = position
+                        .insert(&Coordinates { x: *x, y: *y }, &player)
+                        .ok()?;
+                    Some((player.next(), position))
+                })?;
+        if position.canonical() != canonical {
+            return None;
+        }
+
+        let reply = position
+            .insert(&Coordinates { x: *x, y: *y }, player)
+            .ok()?
+            .canonical();
+        board.available_moves().into_iter().find(|coordinates| {
+            board
+                .insert(coordinates, player)
+                .is_ok_and(|board| board.canonical() == reply)
+        })
+    })
+}
+
+// Every tic-tac-toe position reachable with X moving first, by canonical board.
+// The table is solved once, on first use.
+fn solved() -> &'static HashMap<Board, Outcome> {
+    static SOLVED: OnceLock<HashMap<Board, Outcome>> = OnceLock::new();
+    SOLVED.get_or_init(|| {
+        let mut table = HashMap::new();
+        solve(
+            &Board::new(&Game::TIC_TAC_TOE),
+            &Player::first(),
+            &mut table,
+        );
+        table
+    })
+}
+
+fn solve(board: &Board, player: &Player, table: &mut HashMap<Board, Outcome>) -> Outcome {
+    let key = board.canonical();
+    if let Some(outcome) = table.get(&key) {
+        return outcome.clone();
+    }
+
+    let outcome = match board.outcome(Game::TIC_TAC_TOE.goal) {
+        Outcome::Ongoing => {
+            let outcomes = moves(board, player)
+                .iter()
+                .map(|(_, board)| solve(board, &player.next(), table))
+                .collect::<Vec<Outcome>>();
+            let draw = outcomes
+                .iter()
+                .find(|outcome| matches!(outcome, Outcome::Draw(_)));
+            if outcomes.contains(&Outcome::Won(player.clone())) {
+                Outcome::Won(player.clone())
+            } else if let Some(draw) = draw {
+                draw.clone()
+            } else {
+                Outcome::Won(player.next())
+            }
+        }
+        outcome => outcome,
+    };
+    table.insert(key, outcome.clone());
+
+    outcome
+}
+
+/// The result of `board` under perfect play, X having moved first. Only
+/// tic-tac-toe positions are solved, any other board is `None`.
+pub fn solved_value(board: &Board) -> Option<Outcome> {
+    solved().get(&board.canonical()).cloned()
+}
+
+// The move keeping the best solved value, winning right away when possible.
+fn solved_move(board: &Board, player: &Player) -> Option<Coordinates> {
+    let to_move = if board.count(&Player::X) == board.count(&Player::O) {
+        Player::X
+    } else {
+        Player::O
+    };
+    if *player != to_move || solved_value(board).is_none() {
+        return None;
+    }
+
+    moves(board, player)
+        .into_iter()
+        .min_by_key(|(coordinates, next)| {
+            let value = match solved_value(next) {
+                Some(Outcome::Won(winner)) if winner == *player => 2,
+                Some(Outcome::Draw(_)) => 1,
+                _ => 0,
+            };
+            Reverse((
+                value,
+                next.is_winning_move(coordinates, Game::TIC_TAC_TOE.goal),
+            ))
+        })
+        .map(|(coordinates, _)| coordinates)
+}
+
+// Searches one ply deeper at a time, keeping the last search finished within `budget`.
+// The first ply always completes so there is a move whenever the board isn't full.
+pub fn best_move_timed(
+    game: &Game,
+    board: &Board,
+    player: &Player,
+    budget: Duration,
+) -> Option<Coordinates> {
+    let deadline = Instant::now() + budget;
+    let remaining = board.available_moves().len().min(usize::from(u8::MAX)) as u8;
+    let mut best = best_move_with_depth(game, board, player, 1);
+
+    for depth in 2..=remaining {
+        let mut search = Search {
+            max_depth: Some(depth),
+            deadline: Some(deadline),
+            ..Search::new(game)
+        };
+        let found = search.best_move(board, player);
+        if search.timed_out {
+            break;
+        }
+        best = found;
+    }
+
+    best
+}
+
+// Wins if possible, otherwise blocks the opponent, otherwise searches for a short while.
+// Threats are losing moves in misère, so only the search is used.
+pub fn hint(game: &Game, board: &Board, player: &Player) -> Option<Coordinates> {
+    let threats = if game.misere {
+        vec![]
+    } else {
+        board
+            .threats(player, game.goal)
+            .into_iter()
+            .chain(board.threats(&player.next(), game.goal))
+            .collect()
+    };
+
+    threats
+        .into_iter()
+        .next()
+        .or_else(|| best_move_timed(game, board, player, HINT_BUDGET))
+}
+
+// Every way the game can go from `board`, `player` to move. A game stops at the
+// first completed line, otherwise when no move is left. With a piece limit boards
+// never fill up, so there is no count.
+pub fn count_games(game: &Game, board: &Board, player: &Player) -> Option<u64> {
+    if game.max_pieces_per_player.is_some() {
+        None
+    } else {
+        Some(count_unlimited_games(game, board, player))
+    }
+}
+
+fn count_unlimited_games(game: &Game, board: &Board, player: &Player) -> u64 {
+    let moves = moves(board, player);
+    if moves.is_empty() {
+        return 1;
+    }
+
+    moves
+        .iter()
+        .map(|(coordinates, board)| {
+            if board.is_winning_move(coordinates, game.goal) {
+                1
+            } else {
+                count_unlimited_games(game, board, &player.next())
+            }
+        })
+        .sum()
+}
+
+// Plays `x` against `o` until the game ends. A strategy choosing an illegal move forfeits.
+pub fn simulate(game: &Game, x: &dyn Strategy, o: &dyn Strategy) -> State {
+    let mut state = State::start(game);
+
+    loop {
+        state = match &state {
+            State::NextTurn(player, board) => {
+                let strategy = match player {
+                    Player::X => x,
+                    Player::O => o,
+                };
+                let played = strategy.choose(game, board, player);
+                state
+                    .advance(game, &played)
+                    .unwrap_or_else(|_| State::Won(player.next()))
+            }
+            _ => return state,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_difficulty_from_str() {
+        assert_eq!(Difficulty::from_str("easy"), Ok(Difficulty::Easy));
+        assert_eq!(Difficulty::from_str("Medium"), Ok(Difficulty::Medium));
+        assert_eq!(Difficulty::from_str("HARD"), Ok(Difficulty::Hard));
+        assert!(Difficulty::from_str("impossible").is_err());
+    }
+
+    #[test]
+    fn test_difficulty_next_move() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: -1, y: -1 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: -1, y: 0 }, &Player::O)
+            .unwrap()
+            .insert(&Coordinates { x: 0, y: -1 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 0, y: 0 }, &Player::O)
+            .unwrap();
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for difficulty in [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard].iter() {
+            let coordinates = difficulty
+                .next_move(&game, &board, &Player::X, &mut rng)
+                .unwrap();
+            assert!(board.insert(&coordinates, &Player::X).is_ok());
+        }
+
+        assert_eq!(
+            Difficulty::Medium.next_move(&game, &board, &Player::X, &mut rng),
+            Some(Coordinates { x: 1, y: -1 })
+        );
+        assert_eq!(
+            Difficulty::Hard.next_move(&game, &board, &Player::X, &mut rng),
+            Some(Coordinates { x: 1, y: -1 })
+        );
+    }
+
+    #[test]
+    fn test_next_move_hard_large_board() {
+        let game = Game::GOMOKU;
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: 0, y: 0 }, &Player::X)
+            .unwrap();
+        let mut rng = StdRng::seed_from_u64(42);
+        let (coordinates, _) =
+            Difficulty::Hard.next_move_with_stats(&game, &board, &Player::O, &mut rng);
+
+        assert!(board.insert(&coordinates.unwrap(), &Player::O).is_ok());
+    }
+
+    #[test]
+    fn test_random_move() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: -1, y: -1 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 0, y: 0 }, &Player::O)
+            .unwrap();
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for _ in 0..100 {
+            let coordinates = random_move(&board, &mut rng).unwrap();
+            assert!(board.insert(&coordinates, &Player::X).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_random_move_seeded() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game);
+
+        assert_eq!(
+            random_move(&board, &mut StdRng::seed_from_u64(7)),
+            random_move(&board, &mut StdRng::seed_from_u64(7))
+        );
+    }
+
+    #[test]
+    fn test_random_move_full_board() {
+        let board = Board::from_str("XOX\nXOX\nOXO").unwrap();
+
+        assert_eq!(random_move(&board, &mut StdRng::seed_from_u64(42)), None);
+    }
+
+    #[test]
+    fn test_best_move_takes_win() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: -1, y: -1 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: -1, y: 0 }, &Player::O)
+            .unwrap()
+            .insert(&Coordinates { x: 0, y: -1 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 0, y: 0 }, &Player::O)
+            .unwrap();
+
+        assert_eq!(
+            best_move(&game, &board, &Player::X),
+            Some(Coordinates { x: 1, y: -1 })
+        );
+    }
+
+    #[test]
+    fn test_best_move_blocks_win() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: -1, y: -1 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 0, y: 0 }, &Player::O)
+            .unwrap()
+            .insert(&Coordinates { x: 0, y: -1 }, &Player::X)
+            .unwrap();
+
+        assert_eq!(
+            best_move(&game, &board, &Player::O),
+            Some(Coordinates { x: 1, y: -1 })
+        );
+    }
+
+    // Tries every move sequence for `player` while the AI answers for the other player.
+    fn never_loses(game: &Game, board: &Board, player: &Player) -> bool {
+        moves(board, player)
+            .into_iter()
+            .all(|(coordinates, board)| {
+                if board.is_winning_move(&coordinates, game.goal) {
+                    false
+                } else if board.is_draw() {
+                    true
+                } else {
+                    let ai = player.next();
+                    let coordinates = best_move(game, &board, &ai).unwrap();
+                    let board = board.insert(&coordinates, &ai).unwrap();
+
+                    board.is_winning_move(&coordinates, game.goal)
+                        || board.is_draw()
+                        || never_loses(game, &board, player)
+                }
+            })
+    }
+
+    #[test]
+    fn test_best_move_never_loses() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game);
+
+        assert!(never_loses(&game, &board, &Player::X));
+    }
+
+    #[test]
+    fn test_pruning_visits_fewer_nodes() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: 0, y: 0 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: -1, y: -1 }, &Player::O)
+            .unwrap();
+
+        let mut full = Search {
+            pruning: false,
+            ..Search::new(&game)
+        };
+        let mut pruned = Search::new(&game);
+
+        assert_eq!(
+            full.minimax(&board, &Player::X, 0, -INFINITY, INFINITY),
+            pruned.minimax(&board, &Player::X, 0, -INFINITY, INFINITY)
+        );
+        assert!(pruned.nodes < full.nodes);
+    }
+
+    #[test]
+    fn test_opening_matches_search() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game);
+
+        assert_eq!(
+            opening(&game, &board, &Player::X),
+            best_move(&game, &board, &Player::X)
+        );
+        assert!(opening(&game, &board, &Player::X).is_some());
+    }
+
+    #[test]
+    fn test_opening_symmetric() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: 1, y: 1 }, &Player::O)
+            .unwrap();
+
+        assert_eq!(
+            opening(&game, &board, &Player::X),
+            Some(Coordinates { x: 0, y: 0 })
+        );
+    }
+
+    #[test]
+    fn test_opening_out_of_book() {
+        let board = Board::new(&Game::TIC_TAC_TOE)
+            .insert(&Coordinates { x: 0, y: 0 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: -1, y: -1 }, &Player::O)
+            .unwrap();
+
+        assert_eq!(opening(&Game::TIC_TAC_TOE, &board, &Player::X), None);
+        assert_eq!(
+            opening(
+                &Game::CONNECT_FOUR,
+                &Board::new(&Game::CONNECT_FOUR),
+                &Player::X
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_best_move_with_stats() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: 0, y: 0 }, &Player::X)
+            .unwrap();
+        let (best, stats) = best_move_with_stats(&game, &board, &Player::O);
+
+        assert_eq!(best, best_move(&game, &board, &Player::O));
+        assert!(stats.nodes > 0);
+        assert!(stats.depth > 1);
+    }
+
+    #[test]
+    fn test_count_games() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::from_str("XOX\nOXO\n...").unwrap();
+
+        assert_eq!(count_games(&game, &board, &Player::X), Some(4));
+        assert_eq!(
+            count_games(
+                &game,
+                &Board::from_str("XOX\nXOO\nOXX").unwrap(),
+                &Player::O
+            ),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_count_games_piece_limit() {
+        let game = Game {
+            max_pieces_per_player: Some(3),
+            ..Game::TIC_TAC_TOE
+        };
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: 0, y: 0 }, &Player::X)
+            .unwrap();
+
+        assert_eq!(count_games(&game, &board, &Player::O), None);
+    }
+
+    #[test]
+    fn test_solved_value() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game);
+
+        assert!(matches!(solved_value(&board), Some(Outcome::Draw(_))));
+        assert_eq!(
+            solved_value(
+                &board
+                    .insert(&Coordinates { x: 0, y: 0 }, &Player::X)
+                    .unwrap()
+                    .insert(&Coordinates { x: 0, y: -1 }, &Player::O)
+                    .unwrap()
+            ),
+            Some(Outcome::Won(Player::X))
+        );
+        assert_eq!(solved_value(&Board::new(&Game::CONNECT_FOUR)), None);
+    }
+
+    #[test]
+    fn test_solved_move() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::from_str("XX.\nOO.\n...").unwrap();
+
+        assert_eq!(
+            solved_move(&board, &Player::X),
+            Some(Coordinates { x: 1, y: -1 })
+        );
+        assert_eq!(solved_move(&board, &Player::O), None);
+        assert!(solved_move(&Board::new(&game), &Player::X).is_some());
+    }
+
+    #[test]
+    fn test_table_visits_fewer_nodes() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: 0, y: 0 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 1, y: 0 }, &Player::O)
+            .unwrap();
+
+        let mut uncached = Search {
+            table: None,
+            ..Search::new(&game)
+        };
+        let mut cached = Search::new(&game);
+
+        assert_eq!(
+            cached.best_move(&board, &Player::X),
+            uncached.best_move(&board, &Player::X)
+        );
+        assert!(cached.nodes < uncached.nodes);
+    }
+
+    #[test]
+    fn test_evaluate_empty() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game);
+
+        assert_eq!(evaluate(&game, &board, &Player::X), 0);
+    }
+
+    #[test]
+    fn test_evaluate_longer_run() {
+        let game = Game::GOMOKU;
+        let one = Board::new(&game)
+            .insert(&Coordinates { x: 0, y: 0 }, &Player::X)
+            .unwrap();
+        let two = one.insert(&Coordinates { x: 1, y: 0 }, &Player::X).unwrap();
+
+        assert!(evaluate(&game, &one, &Player::X) > 0);
+        assert!(evaluate(&game, &two, &Player::X) > evaluate(&game, &one, &Player::X));
+        assert_eq!(
+            evaluate(&game, &two, &Player::O),
+            -evaluate(&game, &two, &Player::X)
+        );
+    }
+
+    #[test]
+    fn test_evaluate_blocked_run() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: -1, y: 0 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 0, y: 0 }, &Player::X)
+            .unwrap();
+        let blocked = board
+            .insert(&Coordinates { x: 1, y: 0 }, &Player::O)
+            .unwrap();
+
+        assert!(evaluate(&game, &blocked, &Player::X) < evaluate(&game, &board, &Player::X));
+    }
+
+    #[test]
+    fn test_best_move_with_depth_takes_win() {
+        let game = Game::GOMOKU;
+        let board = (-2..=1).fold(Board::new(&game), |board, x| {
+            board
+                .insert(&Coordinates { x, y: 0 }, &Player::X)
+                .unwrap()
+                .insert(&Coordinates { x, y: 3 }, &Player::O)
+                .unwrap()
+        });
+        let coordinates = best_move_with_depth(&game, &board, &Player::X, 1).unwrap();
+
+        assert!(board
+            .insert(&coordinates, &Player::X)
+            .unwrap()
+            .is_winning_move(&coordinates, game.goal));
+    }
+
+    #[test]
+    fn test_best_move_with_depth_blocks_win() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: -1, y: -1 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 0, y: 0 }, &Player::O)
+            .unwrap()
+            .insert(&Coordinates { x: 0, y: -1 }, &Player::X)
+            .unwrap();
+
+        assert_eq!(
+            best_move_with_depth(&game, &board, &Player::O, 2),
+            Some(Coordinates { x: 1, y: -1 })
+        );
+    }
+
+    #[test]
+    fn test_simulate_perfect_players_draw() {
+        let game = Game::TIC_TAC_TOE;
+
+        assert!(matches!(
+            simulate(&game, &Perfect, &Perfect),
+            State::Draw(_)
+        ));
+    }
+
+    #[test]
+    fn test_simulate_perfect_never_loses() {
+        let game = Game::TIC_TAC_TOE;
+        let random = Random::new(StdRng::seed_from_u64(42));
+
+        for _ in 0..5 {
+            assert!(simulate(&game, &random, &Perfect) != State::Won(Player::X));
+            assert!(simulate(&game, &Perfect, &random) != State::Won(Player::O));
+        }
+    }
+
+    #[test]
+    fn test_simulate_random_players() {
+        let game = Game::TIC_TAC_TOE;
+        let x = Random::new(StdRng::seed_from_u64(1));
+        let o = Random::new(StdRng::seed_from_u64(2));
+
+        for _ in 0..1000 {
+            let state = simulate(&game, &x, &o);
+            assert!(
+                matches!(state, State::Draw(_))
+                    || state == State::Won(Player::X)
+                    || state == State::Won(Player::O)
+            );
+        }
+    }
+
+    #[test]
+    fn test_simulate_same_seed() {
+        struct Recorded<S: Strategy> {
+            strategy: S,
+            moves: Rc<RefCell<Vec<Coordinates>>>,
+        }
+
+        impl<S: Strategy> Strategy for Recorded<S> {
+            fn choose(&self, game: &Game, board: &Board, player: &Player) -> Coordinates {
+                let coordinates = self.strategy.choose(game, board, player);
+                self.moves.borrow_mut().push(coordinates.clone());
+                coordinates
+            }
+        }
+
+        let game = Game::TIC_TAC_TOE;
+        let played = |seed| {
+            let moves = Rc::new(RefCell::new(vec![]));
+            let x = Recorded {
+                strategy: Random::new(StdRng::seed_from_u64(seed)),
+                moves: moves.clone(),
+            };
+            let o = Recorded {
+                strategy: Random::new(StdRng::seed_from_u64(seed + 1)),
+                moves: moves.clone(),
+            };
+            simulate(&game, &x, &o);
+            let moves = moves.borrow().clone();
+            moves
+        };
+
+        assert_eq!(played(7), played(7));
+        assert_ne!(played(7), played(8));
+    }
+
+    #[test]
+    fn test_simulate_illegal_move_forfeits() {
+        struct Corner;
+
+        impl Strategy for Corner {
+            fn choose(&self, _game: &Game, _board: &Board, _player: &Player) -> Coordinates {
+                Coordinates { x: -1, y: -1 }
+            }
+        }
+
+        let game = Game::TIC_TAC_TOE;
+
+        assert!(simulate(&game, &Corner, &Corner) == State::Won(Player::X));
+    }
+
+    #[test]
+    fn test_simulate_gravity() {
+        let game = Game::CONNECT_FOUR;
+        let x = Random::new(StdRng::seed_from_u64(1));
+
+        assert!(simulate(&game, &x, &DepthLimited(2)) != State::Won(Player::X));
+    }
+
+    #[test]
+    fn test_best_move_gravity() {
+        let game = Game::CONNECT_FOUR;
+        let board = (-3..=-1).fold(Board::new(&game), |board, x| {
+            board
+                .insert(&Coordinates { x, y: 0 }, &Player::X)
+                .unwrap()
+                .insert(&Coordinates { x, y: 0 }, &Player::O)
+                .unwrap()
+        });
+
+        assert_eq!(
+            best_move_with_depth(&game, &board, &Player::X, 1),
+            Some(Coordinates { x: 0, y: 3 })
+        );
+    }
+
+    #[test]
+    fn test_best_move_full_board() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::from_str("XOX\nXOX\nOXO").unwrap();
+
+        assert_eq!(best_move(&game, &board, &Player::X), None);
+    }
+
+    #[test]
+    fn test_best_move_timed() {
+        let game = Game::GOMOKU;
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: 0, y: 0 }, &Player::X)
+            .unwrap();
+        let coordinates = best_move_timed(&game, &board, &Player::O, Duration::from_millis(1));
+
+        assert!(coordinates.is_some_and(|c| board.available_moves().contains(&c)));
+    }
+
+    #[test]
+    fn test_best_move_timed_full_search() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::from_str("XX.\nOO.\n...").unwrap();
+
+        assert_eq!(
+            best_move_timed(&game, &board, &Player::X, Duration::from_secs(10)),
+            Some(Coordinates { x: 1, y: -1 })
+        );
+        assert_eq!(
+            best_move_timed(
+                &game,
+                &Board::from_str("XOX\nXOO\nOXX").unwrap(),
+                &Player::O,
+                Duration::from_secs(1)
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_hint_win() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::from_str("XX.\nOO.\n...").unwrap();
+
+        assert_eq!(
+            hint(&game, &board, &Player::X),
+            Some(Coordinates { x: 1, y: -1 })
+        );
+        assert_eq!(
+            hint(&game, &board, &Player::O),
+            Some(Coordinates { x: 1, y: 0 })
+        );
+    }
+
+    #[test]
+    fn test_hint_block() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::from_str("XX.\nO..\n...").unwrap();
+
+        assert_eq!(
+            hint(&game, &board, &Player::O),
+            Some(Coordinates { x: 1, y: -1 })
+        );
+    }
+
+    #[test]
+    fn test_hint_search() {
+        let game = Game::GOMOKU;
+        let board = Board::new(&game);
+
+        assert!(hint(&game, &board, &Player::X).is_some());
+        assert_eq!(
+            hint(
+                &game,
+                &Board::from_str("XOX\nXOO\nOXX").unwrap(),
+                &Player::X
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_best_move_misere() {
+        let game = Game {
+            misere: true,
+            ..Game::TIC_TAC_TOE
+        };
+        let board = Board::from_str("XX.\nO.O\n.O.").unwrap();
+        let coordinates = best_move(&game, &board, &Player::X).unwrap();
+
+        assert_ne!(coordinates, Coordinates { x: 1, y: -1 });
+        assert!(!board
+            .insert(&coordinates, &Player::X)
+            .unwrap()
+            .is_winning_move(&coordinates, game.goal));
+    }
+
+    #[test]
+    fn test_best_move_max_pieces() {
+        let game = Game {
+            max_pieces_per_player: Some(3),
+            ..Game::TIC_TAC_TOE
+        };
+        let board = Board::new(&game);
+
+        assert!(best_move(&game, &board, &Player::X).is_some());
+    }
+
+    #[test]
+    fn test_simulate_misere() {
+        let game = Game {
+            misere: true,
+            ..Game::TIC_TAC_TOE
+        };
+
+        assert!(matches!(
+            simulate(&game, &Perfect, &Perfect),
+            State::Draw(_)
+        ));
+    }
+}
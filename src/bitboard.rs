@@ -0,0 +1,403 @@
+use std::fmt;
+
+use crate::board::Board;
+use crate::coordinates::{Coord, Coordinates};
+use crate::direction::Direction;
+use crate::error::MoveError;
+use crate::game::{Game, GravityDirection, OverlineRule};
+use crate::player::Player;
+
+// Cells are stored row by row with one spare bit at the end of each row, so
+// shifting along a row never wraps onto the next one.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct BitBoard {
+    x: u128,
+    o: u128,
+
+    min_x: Coord,
+    max_x: Coord,
+    min_y: Coord,
+    max_y: Coord,
+
+    gravity: bool,
+
+    symbols: [char; 2],
+}
+
+impl BitBoard {
+    pub fn new(game: &Game) -> Result<BitBoard, String> {
+        let width = i32::from(game.max_x) - i32::from(game.min_x) + 1;
+        let height = i32::from(game.max_y) - i32::from(game.min_y) + 1;
+        if (width + 1) * height > 128 {
+            Err(format!(
+                "A {}x{} board doesn't fit in a bitboard",
+                width, height
+            ))
+        } else if game.diagonal_goal.is_some_and(|goal| goal != game.goal) {
+            Err("A bitboard only checks a single goal".to_string())
+        } else if game.overline == OverlineRule::Forbidden {
+            Err("A bitboard doesn't check for overlines".to_string())
+        } else if game.gravity && game.gravity_direction != GravityDirection::Down {
+            Err("A bitboard only drops pieces down".to_string())
+        } else if game.max_pieces_per_player.is_some() {
+            Err("A bitboard doesn't keep the order pieces were played in".to_string())
+        } else {
+            Ok(BitBoard {
+                x: 0,
+                o: 0,
+                min_x: game.min_x,
+                max_x: game.max_x,
+                min_y: game.min_y,
+                max_y: game.max_y,
+                gravity: game.gravity,
+                symbols: game.symbols,
+            })
+        }
+    }
+
+    fn stride(&self) -> u32 {
+        (self.max_x - self.min_x + 2) as u32
+    }
+
+    fn on_board(&self, coordinates: &Coordinates) -> bool {
+        coordinates.x >= self.min_x
+            && coordinates.x <= self.max_x
+            && coordinates.y >= self.min_y
+            && coordinates.y <= self.max_y
+    }
+
+    fn index(&self, coordinates: &Coordinates) -> u32 {
+        (coordinates.y - self.min_y) as u32 * self.stride() + (coordinates.x - self.min_x) as u32
+    }
+
+    fn bits(&self, player: &Player) -> u128 {
+        match player {
+            Player::X => self.x,
+            Player::O => self.o,
+        }
+    }
+
+    fn is_free(&self, coordinates: &Coordinates) -> bool {
+        (self.x | self.o) & (1 << self.index(coordinates)) == 0
+    }
+
+    fn resolve(&self, coordinates: &Coordinates) -> Result<Coordinates, MoveError> {
+        if !self.gravity {
+            Ok(coordinates.clone())
+        } else if coordinates.x < self.min_x || coordinates.x > self.max_x {
+            Err(MoveError::OutOfBounds)
+        } else {
+            (self.min_y..=self.max_y)
+                .rev()
+                .map(|y| Coordinates {
+                    x: coordinates.x,
+                    y,
+                })
+                .find(|coordinates| self.is_free(coordinates))
+                .ok_or(MoveError::ColumnFull)
+        }
+    }
+
+    pub fn insert(
+        &self,
+        coordinates: &Coordinates,
+        player: &Player,
+    ) -> Result<BitBoard, MoveError> {
+        let coordinates = &self.resolve(coordinates)?;
+        if !self.on_board(coordinates) {
+            Err(MoveError::OutOfBounds)
+        } else if !self.is_free(coordinates) {
+            Err(MoveError::AlreadyDefined)
+        } else {
+            let bit = 1 << self.index(coordinates);
+            let mut board = *self;
+            match player {
+                Player::X => board.x |= bit,
+                Player::O => board.o |= bit,
+            }
+            Ok(board)
+        }
+    }
+
+    pub fn get(&self, coordinates: &Coordinates) -> Option<Player> {
+        if !self.on_board(coordinates) {
+            None
+        } else {
+            let bit = 1 << self.index(coordinates);
+            if self.x & bit != 0 {
+                Some(Player::X)
+            } else if self.o & bit != 0 {
+                Some(Player::O)
+            } else {
+                None
+            }
+        }
+    }
+
+    // For each direction, `runs` keeps the bits starting `goal` aligned pieces.
+    // The move wins if one of those runs starts close enough to cover it.
+    pub fn is_winning_move(&self, coordinates: &Coordinates, goal: i8) -> bool {
+        let player = match self.get(coordinates) {
+            Some(player) => player,
+            None => return false,
+        };
+        let bits = self.bits(&player);
+        let index = self.index(coordinates);
+        let stride = self.stride();
+
+        let goal = goal.max(1) as u32;
+
+        Direction::ALL.iter().any(|direction| {
+            let (dx, dy) = direction.delta();
+            let shift = (i32::from(dy) * stride as i32 + i32::from(dx)).unsigned_abs();
+            (goal - 1) * shift < 128 && {
+                let runs = (1..goal).fold(bits, |runs, step| runs & (bits >> (shift * step)));
+                let starts = (0..goal)
+                    .filter_map(|step| index.checked_sub(shift * step))
+                    .fold(0, |starts, start| starts | (1 << start));
+
+                runs & starts != 0
+            }
+        })
+    }
+
+    pub fn is_draw(&self) -> bool {
+        let cell_amount =
+            (self.max_x - self.min_x + 1) as u32 * (self.max_y - self.min_y + 1) as u32;
+        (self.x | self.o).count_ones() >= cell_amount
+    }
+
+    fn to_board(self) -> Board {
+        let game = Game {
+            min_x: self.min_x,
+            max_x: self.max_x,
+            min_y: self.min_y,
+            max_y: self.max_y,
+            goal: 1,
+            diagonal_goal: None,
+            overline: OverlineRule::Allowed,
+            gravity: false,
+            gravity_direction: GravityDirection::Down,
+            misere: false,
+            max_pieces_per_player: None,
+            symbols: self.symbols,
+        };
+
+        (self.min_y..=self.max_y)
+            .flat_map(|y| (self.min_x..=self.max_x).map(move |x| Coordinates { x, y }))
+            .fold(Board::new(&game), |board, coordinates| {
+                match self.get(&coordinates) {
+                    Some(player) => board.insert(&coordinates, &player).unwrap_or(board),
+                    None => board,
+                }
+            })
+    }
+}
+
+impl fmt::Display for BitBoard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_board())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    // Counts the allocations made by the current thread, tests run in parallel.
+    struct Counting;
+
+    thread_local! {
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            ALLOCATIONS.with(|allocations| allocations.set(allocations.get() + 1));
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+
+    fn allocations<F: FnOnce()>(f: F) -> usize {
+        let before = ALLOCATIONS.with(Cell::get);
+        f();
+        ALLOCATIONS.with(Cell::get) - before
+    }
+
+    #[test]
+    fn test_new_too_large() {
+        assert!(BitBoard::new(&Game::TIC_TAC_TOE).is_ok());
+        assert!(BitBoard::new(&Game::CONNECT_FOUR).is_ok());
+        assert!(BitBoard::new(&Game::GOMOKU).is_err());
+    }
+
+    #[test]
+    fn test_new_diagonal_goal() {
+        let game = Game {
+            diagonal_goal: Some(2),
+            ..Game::TIC_TAC_TOE
+        };
+
+        assert!(BitBoard::new(&game).is_err());
+    }
+
+    #[test]
+    fn test_new_gravity_direction() {
+        let game = Game {
+            gravity_direction: GravityDirection::Left,
+            ..Game::CONNECT_FOUR
+        };
+
+        assert!(BitBoard::new(&game).is_err());
+    }
+
+    #[test]
+    fn test_new_overline() {
+        let game = Game {
+            overline: OverlineRule::Forbidden,
+            ..Game::GOMOKU
+        };
+
+        assert!(BitBoard::new(&game).is_err());
+    }
+
+    #[test]
+    fn test_new_max_pieces() {
+        let game = Game {
+            max_pieces_per_player: Some(3),
+            ..Game::TIC_TAC_TOE
+        };
+
+        assert!(BitBoard::new(&game).is_err());
+    }
+
+    #[test]
+    fn test_insert() {
+        let game = Game::TIC_TAC_TOE;
+        let coordinates = Coordinates { x: 0, y: 0 };
+        let board = BitBoard::new(&game).unwrap();
+        let next = board.insert(&coordinates, &Player::X).unwrap();
+
+        assert_eq!(board.get(&coordinates), None);
+        assert_eq!(next.get(&coordinates), Some(Player::X));
+        assert_eq!(
+            next.insert(&coordinates, &Player::O),
+            Err(MoveError::AlreadyDefined)
+        );
+        assert_eq!(
+            next.insert(&Coordinates { x: 2, y: 0 }, &Player::O),
+            Err(MoveError::OutOfBounds)
+        );
+    }
+
+    #[test]
+    fn test_insert_gravity() {
+        let game = Game::CONNECT_FOUR;
+        let board = BitBoard::new(&game)
+            .unwrap()
+            .insert(&Coordinates { x: 0, y: -2 }, &Player::X)
+            .unwrap();
+
+        assert_eq!(board.get(&Coordinates { x: 0, y: 3 }), Some(Player::X));
+    }
+
+    #[test]
+    fn test_insert_does_not_allocate() {
+        let game = Game::TIC_TAC_TOE;
+        let coordinates = Coordinates { x: 0, y: 0 };
+        let bitboard = BitBoard::new(&game).unwrap();
+        let board = Board::new(&game);
+
+        assert_eq!(
+            allocations(|| {
+                bitboard.insert(&coordinates, &Player::X).unwrap();
+            }),
+            0
+        );
+        assert!(
+            allocations(|| {
+                board.insert(&coordinates, &Player::X).unwrap();
+            }) > 0
+        );
+    }
+
+    fn winning(game: &Game, cells: &[(Coord, Coord)]) -> bool {
+        let (last, board) = cells.iter().fold(
+            (None, BitBoard::new(game).unwrap()),
+            |(_, board), (x, y)| {
+                let coordinates = Coordinates { x: *x, y: *y };
+                let board = board.insert(&coordinates, &Player::X).unwrap();
+                (Some(coordinates), board)
+            },
+        );
+
+        board.is_winning_move(&last.unwrap(), game.goal)
+    }
+
+    #[test]
+    fn test_is_winning_move() {
+        let game = Game::TIC_TAC_TOE;
+
+        assert!(winning(&game, &[(-1, -1), (0, -1), (1, -1)]));
+        assert!(winning(&game, &[(0, -1), (0, 1), (0, 0)]));
+        assert!(winning(&game, &[(-1, -1), (1, 1), (0, 0)]));
+        assert!(winning(&game, &[(1, -1), (-1, 1), (0, 0)]));
+        assert!(!winning(&game, &[(1, -1), (-1, 0), (0, 0)]));
+        assert!(!winning(&game, &[(1, 0), (-1, 1), (0, 0)]));
+    }
+
+    #[test]
+    fn test_is_winning_move_connect_four() {
+        let game = Game::CONNECT_FOUR;
+
+        assert!(winning(&game, &[(-3, 3), (-2, 3), (-1, 3), (0, 3)]));
+        assert!(!winning(&game, &[(1, 0), (2, 0), (3, 0), (-3, 0)]));
+        assert!(winning(&game, &[(0, 0), (1, 1), (2, 2), (3, 3)]));
+        assert!(winning(&game, &[(-3, -2), (-3, -1), (-3, 0), (-3, 1)]));
+    }
+
+    #[test]
+    fn test_is_winning_move_last_cell() {
+        let game = Game::CONNECT_FOUR;
+        let board = (-2..=3).fold(BitBoard::new(&game).unwrap(), |board, y| {
+            board.insert(&Coordinates { x: 3, y }, &Player::O).unwrap()
+        });
+
+        assert!(board.is_winning_move(&Coordinates { x: 3, y: 3 }, game.goal));
+    }
+
+    #[test]
+    fn test_is_draw() {
+        let game = Game::TIC_TAC_TOE;
+        let board = (-1..=1)
+            .flat_map(|y| (-1..=1).map(move |x| Coordinates { x, y }))
+            .fold(BitBoard::new(&game).unwrap(), |board, coordinates| {
+                board.insert(&coordinates, &Player::X).unwrap()
+            });
+
+        assert!(board.is_draw());
+        assert!(!BitBoard::new(&game).unwrap().is_draw());
+    }
+
+    #[test]
+    fn test_to_string() {
+        let game = Game::TIC_TAC_TOE;
+        let coordinates = Coordinates { x: 0, y: 0 };
+        let bitboard = BitBoard::new(&game)
+            .unwrap()
+            .insert(&coordinates, &Player::X)
+            .unwrap();
+        let board = Board::new(&game).insert(&coordinates, &Player::X).unwrap();
+
+        assert_eq!(bitboard.to_string(), board.to_string());
+    }
+}
@@ -0,0 +1,2976 @@
+use im::{HashMap, HashSet, Vector};
+use itertools::Itertools;
+#[cfg(not(feature = "serde"))]
+use regex::Regex;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::convert::TryFrom;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+#[cfg(not(feature = "serde"))]
+use std::sync::OnceLock;
+
+use crate::coordinates::{Coord, Coordinates};
+use crate::direction::Direction;
+use crate::error::MoveError;
+use crate::game::{Game, GravityDirection, OverlineRule};
+use crate::outcome::{DrawReason, Outcome};
+use crate::player::{Player, COLOR_RESET};
+
+// How `Board::render` lays out the grid. By default cells are wide enough for
+// the longest `x,y` label.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct RenderOptions {
+    pub cell_width: Option<usize>,
+    pub column_separator: char,
+    pub row_separator: char,
+    pub colored: bool,
+    pub numbered: bool,
+    pub highlighted: Vec<Coordinates>,
+}
+
+impl Default for RenderOptions {
+    fn default() -> RenderOptions {
+        RenderOptions {
+            cell_width: None,
+            column_separator: '|',
+            row_separator: '-',
+            colored: false,
+            numbered: false,
+            highlighted: vec![],
+        }
+    }
+}
+
+// What playing an empty cell would do. Winning takes precedence when a cell
+// also blocks the opponent.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum MoveKind {
+    Winning,
+    Blocks,
+    Neutral,
+}
+
+// Cells and history are persistent collections, so cloning a board is cheap and
+// `insert` shares everything but the new cell with the previous board.
+// Equality and hashing are about the position, not how it was reached, so the
+// move history is left out of both.
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(into = "BoardData", try_from = "BoardData")
+)]
+pub struct Board {
+    hash: HashMap<Coordinates, Player>,
+    history: Vector<(Coordinates, Player)>,
+    blocked: HashSet<Coordinates>,
+
+    min_x: Coord,
+    max_x: Coord,
+    min_y: Coord,
+    max_y: Coord,
+
+    goal: i8,
+
+    diagonal_goal: Option<i8>,
+
+    overline: OverlineRule,
+
+    gravity: bool,
+
+    gravity_direction: GravityDirection,
+
+    misere: bool,
+
+    max_pieces_per_player: Option<u8>,
+
+    symbols: [char; 2],
+}
+
+impl Board {
+    fn on_board(&self, coordinates: &Coordinates) -> bool {
+        coordinates.x >= self.min_x
+            && coordinates.x <= self.max_x
+            && coordinates.y >= self.min_y
+            && coordinates.y <= self.max_y
+    }
+
+    // The inclusive `(min_x, max_x, min_y, max_y)` extents of the board.
+    pub fn bounds(&self) -> (Coord, Coord, Coord, Coord) {
+        (self.min_x, self.max_x, self.min_y, self.max_y)
+    }
+
+    pub fn width(&self) -> Coord {
+        self.max_x - self.min_x + 1
+    }
+
+    pub fn height(&self) -> Coord {
+        self.max_y - self.min_y + 1
+    }
+
+    // `goal` holds for rows and columns, diagonals may have their own.
+    fn goal_along(&self, direction: Direction, goal: i8) -> i8 {
+        match direction {
+            Direction::Diagonal | Direction::AntiDiagonal => self.diagonal_goal.unwrap_or(goal),
+            Direction::Horizontal | Direction::Vertical => goal,
+        }
+    }
+
+    fn symbol(&self, player: &Player) -> char {
+        match player {
+            Player::X => self.symbols[0],
+            Player::O => self.symbols[1],
+        }
+    }
+
+    // The game isn't validated, a goal longer than every line can only end in a draw.
+    pub fn new(game: &Game) -> Board {
+        let hash = HashMap::new();
+        Board {
+            hash,
+            history: Vector::new(),
+            blocked: HashSet::new(),
+            min_x: game.min_x,
+            max_x: game.max_x,
+            min_y: game.min_y,
+            max_y: game.max_y,
+            goal: game.goal,
+            diagonal_goal: game.diagonal_goal,
+            overline: game.overline,
+            gravity: game.gravity,
+            gravity_direction: game.gravity_direction,
+            misere: game.misere,
+            max_pieces_per_player: game.max_pieces_per_player,
+            symbols: game.symbols,
+        }
+    }
+
+    // The same game with every piece taken off. Blocked cells are part of the layout
+    // and stay.
+    pub fn reset(&self) -> Board {
+        Board {
+            hash: HashMap::new(),
+            history: Vector::new(),
+            ..self.clone()
+        }
+    }
+
+    // Unlike `new`, refuses hand built games with inverted bounds or an unreachable goal.
+    pub fn try_new(game: &Game) -> Result<Board, String> {
+        game.validate().map(|_| Board::new(game))
+    }
+
+    // The last free cell sliding from the opposite side towards `direction`, along
+    // the column or row of `coordinates`.
+    fn landing(
+        &self,
+        coordinates: &Coordinates,
+        direction: GravityDirection,
+    ) -> Result<Coordinates, MoveError> {
+        let Coordinates { x, y } = coordinates.clone();
+        let line = match direction {
+            GravityDirection::Down | GravityDirection::Up if x < self.min_x || x > self.max_x => {
+                return Err(MoveError::OutOfBounds)
+            }
+            GravityDirection::Left | GravityDirection::Right
+                if y < self.min_y || y > self.max_y =>
+            {
+                return Err(MoveError::OutOfBounds)
+            }
+            GravityDirection::Down => (self.min_y..=self.max_y)
+                .map(|y| Coordinates { x, y })
+                .collect::<Vec<Coordinates>>(),
+            GravityDirection::Up => (self.min_y..=self.max_y)
+                .rev()
+                .map(|y| Coordinates { x, y })
+                .collect(),
+            GravityDirection::Right => (self.min_x..=self.max_x)
+                .map(|x| Coordinates { x, y })
+                .collect(),
+            GravityDirection::Left => (self.min_x..=self.max_x)
+                .rev()
+                .map(|x| Coordinates { x, y })
+                .collect(),
+        };
+
+        line.into_iter()
+            .take_while(|coordinates| self.is_free(coordinates))
+            .last()
+            .ok_or(MoveError::ColumnFull)
+    }
+
+    fn is_free(&self, coordinates: &Coordinates) -> bool {
+        !self.hash.contains_key(coordinates) && !self.blocked.contains(coordinates)
+    }
+
+    pub fn is_blocked(&self, coordinates: &Coordinates) -> bool {
+        self.blocked.contains(coordinates)
+    }
+
+    // A blocked cell can't be played by anyone and breaks every line through it.
+    pub fn block(&self, coordinates: &Coordinates) -> Result<Board, MoveError> {
+        if !self.on_board(coordinates) {
+            Err(MoveError::OutOfBounds)
+        } else if !self.is_free(coordinates) {
+            Err(MoveError::AlreadyDefined)
+        } else {
+            Ok(Board {
+                blocked: self.blocked.update(coordinates.clone()),
+                ..self.clone()
+            })
+        }
+    }
+
+    // With gravity, pieces fall down the column, or any other way the game says,
+    // until the first piece or blocked cell.
+    pub fn resolve(&self, coordinates: &Coordinates) -> Result<Coordinates, MoveError> {
+        if self.gravity {
+            self.landing(coordinates, self.gravity_direction)
+        } else {
+            Ok(coordinates.clone())
+        }
+    }
+
+    // Drops a piece down `column` whether or not the game has gravity.
+    pub fn drop(&self, column: Coord, player: &Player) -> Result<(Board, Coordinates), MoveError> {
+        let top = Coordinates {
+            x: column,
+            y: self.min_y,
+        };
+        self.landing(&top, GravityDirection::Down)
+            .and_then(|coordinates| {
+                self.put(&coordinates, player)
+                    .map(|(board, _)| (board, coordinates))
+            })
+    }
+
+    pub fn insert(&self, coordinates: &Coordinates, player: &Player) -> Result<Board, MoveError> {
+        self.place(coordinates, player).map(|(board, _)| board)
+    }
+
+    // Like `insert`, also telling which cell was vacated when the player was
+    // already at their piece limit.
+    pub fn place(
+        &self,
+        coordinates: &Coordinates,
+        player: &Player,
+    ) -> Result<(Board, Option<Coordinates>), MoveError> {
+        self.put(&self.resolve(coordinates)?, player)
+    }
+
+    // Like `place` on the very cell given, whatever the game's gravity.
+    fn put(
+        &self,
+        coordinates: &Coordinates,
+        player: &Player,
+    ) -> Result<(Board, Option<Coordinates>), MoveError> {
+        if !self.on_board(coordinates) {
+            Err(MoveError::OutOfBounds)
+        } else if !self.is_free(coordinates) {
+            Err(MoveError::AlreadyDefined)
+        } else {
+            let oldest = self
+                .max_pieces_per_player
+                .filter(|max| self.count(player) >= usize::from(*max))
+                .and_then(|_| self.history.iter().find(|(_, p)| p == player))
+                .map(|(coordinates, _)| coordinates.clone());
+            let board = match &oldest {
+                Some(oldest) => self.remove(oldest)?,
+                None => self.clone(),
+            };
+
+            let mut hash = board.hash.clone();
+            hash.insert(coordinates.clone(), player.clone());
+            let mut history = board.history.clone();
+            history.push_back((coordinates.clone(), player.clone()));
+
+            Ok((
+                Board {
+                    hash,
+                    history,
+                    ..board
+                },
+                oldest,
+            ))
+        }
+    }
+
+    pub fn remove(&self, coordinates: &Coordinates) -> Result<Board, MoveError> {
+        if !self.hash.contains_key(coordinates) {
+            Err(MoveError::NotDefined)
+        } else {
+            let mut hash = self.hash.clone();
+            hash.remove(coordinates);
+            let mut history = self.history.clone();
+            history.retain(|(c, _)| c != coordinates);
+
+            Ok(Board {
+                hash,
+                history,
+                ..self.clone()
+            })
+        }
+    }
+
+    pub fn moves(&self) -> Vec<(Coordinates, Player)> {
+        self.history.iter().cloned().collect()
+    }
+
+    // Empty and out of bounds cells are both None.
+    pub fn get(&self, coordinates: &Coordinates) -> Option<&Player> {
+        self.hash.get(coordinates)
+    }
+
+    pub fn count(&self, player: &Player) -> usize {
+        self.hash.values().filter(|p| *p == player).count()
+    }
+
+    pub fn occupied(&self) -> usize {
+        self.hash.len()
+    }
+
+    // Blocked cells don't count, only pieces do.
+    pub fn is_empty(&self) -> bool {
+        self.hash.is_empty()
+    }
+
+    // Only the pieces, in no particular order.
+    pub fn occupied_cells(&self) -> impl Iterator<Item = (&Coordinates, &Player)> {
+        self.hash.iter()
+    }
+
+    // Every cell row by row, whatever order the pieces were played in.
+    pub fn cells(&self) -> impl Iterator<Item = (Coordinates, Option<&Player>)> {
+        (self.min_y..=self.max_y)
+            .flat_map(move |y| (self.min_x..=self.max_x).map(move |x| Coordinates { x, y }))
+            .map(move |coordinates| {
+                let player = self.hash.get(&coordinates);
+                (coordinates, player)
+            })
+    }
+
+    // Row by row from `min_y`, each row from `min_x`, for frontends drawing cells themselves.
+    pub fn grid(&self) -> Vec<Vec<Option<Player>>> {
+        self.rows()
+            .into_iter()
+            .map(|row| row.into_iter().map(|(_, player)| player.cloned()).collect())
+            .collect()
+    }
+
+    fn rows(&self) -> Vec<Vec<(Coordinates, Option<&Player>)>> {
+        let width = (self.min_x..=self.max_x).len();
+        self.cells()
+            .collect::<Vec<(Coordinates, Option<&Player>)>>()
+            .chunks(width)
+            .map(|row| row.to_vec())
+            .collect()
+    }
+
+    pub fn empty_cells(&self) -> Vec<Coordinates> {
+        self.cells()
+            .filter(|(coordinates, player)| player.is_none() && !self.is_blocked(coordinates))
+            .map(|(coordinates, _)| coordinates)
+            .collect()
+    }
+
+    // Empty cells a piece can land on, leaving out those gravity would move.
+    pub fn available_moves(&self) -> Vec<Coordinates> {
+        self.empty_cells()
+            .into_iter()
+            .filter(|coordinates| self.resolve(coordinates).as_ref() == Ok(coordinates))
+            .collect()
+    }
+
+    // Cells where `player` would win right away.
+    pub fn threats(&self, player: &Player, goal: i8) -> Vec<Coordinates> {
+        self.available_moves()
+            .into_iter()
+            .filter(|coordinates| {
+                self.insert(coordinates, player)
+                    .is_ok_and(|board| board.is_winning_move(coordinates, goal))
+            })
+            .collect()
+    }
+
+    pub fn move_analysis(&self, player: &Player, goal: i8) -> Vec<(Coordinates, MoveKind)> {
+        let winning = self.threats(player, goal);
+        let blocking = self.threats(&player.next(), goal);
+
+        self.available_moves()
+            .into_iter()
+            .map(|coordinates| {
+                let kind = if winning.contains(&coordinates) {
+                    MoveKind::Winning
+                } else if blocking.contains(&coordinates) {
+                    MoveKind::Blocks
+                } else {
+                    MoveKind::Neutral
+                };
+                (coordinates, kind)
+            })
+            .collect()
+    }
+
+    // Only tells whether no move is left, `outcome` also looks for a winner. Blocked
+    // cells and, with gravity, the cells under them are never playable.
+    pub fn is_draw(&self) -> bool {
+        self.available_moves().is_empty()
+    }
+
+    // Why the game can't be won anymore, a full board taking precedence. With a
+    // piece limit, the oldest pieces go away and mixed lines can open up again.
+    pub fn draw_reason(&self, goal: i8) -> Option<DrawReason> {
+        if self.is_draw() {
+            Some(DrawReason::BoardFull)
+        } else if self.max_pieces_per_player.is_none() && self.is_forced_draw(goal) {
+            Some(DrawReason::NoWinningLinePossible)
+        } else {
+            None
+        }
+    }
+
+    // A completed line takes precedence over a full or blocked board. In misère
+    // games, the player completing it loses.
+    pub fn outcome(&self, goal: i8) -> Outcome {
+        match self.winner(goal) {
+            Some(player) if self.misere => Outcome::Won(player.next()),
+            Some(player) => Outcome::Won(player),
+            None => self
+                .draw_reason(goal)
+                .map_or(Outcome::Ongoing, Outcome::Draw),
+        }
+    }
+
+    // Every stretch of `goal` cells holds pieces of both players or a blocked
+    // cell, so nobody can win.
+    pub fn is_forced_draw(&self, goal: i8) -> bool {
+        self.lines_with_direction()
+            .iter()
+            .flat_map(|(direction, line)| {
+                line.windows(self.goal_along(*direction, goal).max(1) as usize)
+            })
+            .all(|window| {
+                if window
+                    .iter()
+                    .any(|coordinates| self.is_blocked(coordinates))
+                {
+                    return true;
+                }
+                let mut players = window
+                    .iter()
+                    .filter_map(|coordinates| self.hash.get(coordinates));
+                players
+                    .next()
+                    .is_some_and(|first| players.any(|p| p != first))
+            })
+    }
+
+    // Rows shorter than the goal can't hold a winning line and are left out.
+    pub fn affected_rows(&self, coordinates: &Coordinates) -> Vec<Vec<Coordinates>> {
+        self.affected_rows_with_direction(coordinates, self.goal)
+            .into_iter()
+            .map(|(_, row)| row)
+            .collect()
+    }
+
+    // Rows are kept against `goal`, which may be shorter than the game's.
+    pub fn affected_rows_with_direction(
+        &self,
+        coordinates: &Coordinates,
+        goal: i8,
+    ) -> Vec<(Direction, Vec<Coordinates>)> {
+        let size = (self.max_x - self.min_x).max(self.max_y - self.min_y);
+        let offsets = -size..=size;
+
+        Direction::ALL
+            .iter()
+            .map(|direction| {
+                let (dx, dy) = direction.delta();
+                let row = offsets
+                    .clone()
+                    .map(|d| coordinates.translate(Coord::from(dx) * d, Coord::from(dy) * d))
+                    .filter(|coordinates| self.on_board(coordinates))
+                    .collect::<Vec<Coordinates>>();
+                (*direction, row)
+            })
+            .filter(|(direction, row)| {
+                row.contains(coordinates) && row.len() >= self.goal_along(*direction, goal) as usize
+            })
+            .unique_by(|(_, row)| row.clone())
+            .collect()
+    }
+
+    // Cells are sorted by y then x so the output doesn't depend on the map's ordering.
+    pub fn to_json(&self) -> String {
+        let cells = self
+            .hash
+            .iter()
+            .sorted_by_key(|(coordinates, _)| (coordinates.y, coordinates.x))
+            .map(|(coordinates, player)| {
+                format!(
+                    r#"{{"x":{},"y":{},"player":"{}"}}"#,
+                    coordinates.x,
+                    coordinates.y,
+                    player.to_char()
+                )
+            })
+            .join(",");
+        // Only written when there are any, so plain boards keep their shape.
+        let blocked = if self.blocked.is_empty() {
+            String::new()
+        } else {
+            let cells = self
+                .blocked
+                .iter()
+                .sorted_by_key(|coordinates| (coordinates.y, coordinates.x))
+                .map(|coordinates| format!(r#"{{"x":{},"y":{}}}"#, coordinates.x, coordinates.y))
+                .join(",");
+            format!(r#","blocked":[{}]"#, cells)
+        };
+
+        format!(
+            r#"{{"min_x":{},"max_x":{},"min_y":{},"max_y":{},"goal":{},"cells":[{}]{}}}"#,
+            self.min_x, self.max_x, self.min_y, self.max_y, self.goal, cells, blocked
+        )
+    }
+
+    pub fn from_json(s: &str, game: &Game) -> Result<Board, String> {
+        let json = BoardJson::parse(s).ok_or_else(|| "Board can't be parsed".to_string())?;
+        if (json.min_x, json.max_x, json.min_y, json.max_y, json.goal)
+            != (game.min_x, game.max_x, game.min_y, game.max_y, game.goal)
+        {
+            return Err("Board doesn't match the game".to_string());
+        }
+
+        // Cells are put where they were written, gravity was already applied.
+        json.blocked
+            .iter()
+            .try_fold(Board::new(game), |board, coordinates| {
+                board.block(coordinates)
+            })
+            .and_then(|board| {
+                json.cells.into_iter().try_fold(board, |board, cell| {
+                    board
+                        .put(
+                            &Coordinates {
+                                x: cell.x,
+                                y: cell.y,
+                            },
+                            &cell.player,
+                        )
+                        .map(|(board, _)| board)
+                })
+            })
+            .map_err(String::from)
+    }
+
+    // Wraps player symbols in ANSI escape codes, for terminals.
+    pub fn to_string_colored(&self) -> String {
+        self.render(&RenderOptions {
+            colored: true,
+            ..RenderOptions::default()
+        })
+    }
+
+    // Surrounds the given cells with brackets, e.g. to show a winning line.
+    pub fn to_string_highlighted(&self, cells: &[Coordinates]) -> String {
+        self.render(&RenderOptions {
+            highlighted: cells.to_vec(),
+            ..RenderOptions::default()
+        })
+    }
+
+    // Labels empty cells with the numbers accepted as moves instead of `x,y`.
+    pub fn to_string_numbered(&self) -> String {
+        self.render(&RenderOptions {
+            numbered: true,
+            ..RenderOptions::default()
+        })
+    }
+
+    // One character per cell, `.` for the empty ones and `#` for the blocked ones.
+    pub fn to_string_compact(&self) -> String {
+        self.rows()
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|(coordinates, player)| match player {
+                        Some(player) => self.symbol(player),
+                        None if self.is_blocked(coordinates) => '#',
+                        None => '.',
+                    })
+                    .collect::<String>()
+            })
+            .join("\n")
+    }
+
+    // Axis labels around the board, cells only showing the players.
+    pub fn to_string_with_headers(&self) -> String {
+        let label_size = [self.min_y, self.max_y]
+            .iter()
+            .map(|y| y.to_string().len())
+            .max()
+            .unwrap();
+        let cell_size = [self.min_x, self.max_x]
+            .iter()
+            .map(|x| x.to_string().len())
+            .max()
+            .unwrap();
+
+        let header = (self.min_x..=self.max_x)
+            .map(|x| format!(" {: >1$}", x, cell_size))
+            .join("");
+        let rows = (self.min_y..=self.max_y).map(|y| {
+            let cells = (self.min_x..=self.max_x)
+                .map(|x| {
+                    let symbol = self
+                        .hash
+                        .get(&Coordinates { x, y })
+                        .map_or(' ', |player| self.symbol(player));
+                    let symbol = if self.is_blocked(&Coordinates { x, y }) {
+                        '#'
+                    } else {
+                        symbol
+                    };
+                    format!(" {: >1$}", symbol, cell_size)
+                })
+                .join("");
+            format!("{: >1$} {2}", y, label_size, cells)
+        });
+
+        std::iter::once(format!("{: >1$} {2}", "", label_size, header))
+            .chain(rows)
+            .map(|row| row.trim_end().to_string())
+            .join("\n")
+    }
+
+    // Labels that don't fit in `cell_width` are left out, brackets around
+    // highlighted cells included.
+    pub fn render(&self, options: &RenderOptions) -> String {
+        let cell_size = options.cell_width.unwrap_or_else(|| {
+            [self.min_x, self.max_x, self.min_y, self.max_y]
+                .iter()
+                .map(|s| s.to_string().len())
+                .max()
+                .unwrap()
+                * 2
+                + 3
+        });
+        let fit = |label: String| {
+            if label.len() <= cell_size {
+                label
+            } else {
+                String::new()
+            }
+        };
+        let line_split = vec![
+            options.row_separator.to_string().repeat(cell_size);
+            (self.min_x..=self.max_x).len()
+        ];
+
+        let rows = self.rows().into_iter().map(|row| {
+            row.into_iter()
+                .map(|(coordinates, player)| match player {
+                    Some(player) => {
+                        let symbol = if options.highlighted.contains(&coordinates) {
+                            format!("[{}]", self.symbol(player))
+                        } else {
+                            self.symbol(player).to_string()
+                        };
+                        let symbol = match fit(symbol) {
+                            symbol if symbol.is_empty() => self.symbol(player).to_string(),
+                            symbol => symbol,
+                        };
+                        let cell = format!("{: ^1$}", symbol, cell_size);
+                        if options.colored {
+                            let painted = format!("{}{}{}", player.color(), symbol, COLOR_RESET);
+                            cell.replacen(&symbol, &painted, 1)
+                        } else {
+                            cell
+                        }
+                    }
+                    None if self.is_blocked(&coordinates) => format!("{: ^1$}", "#", cell_size),
+                    None if options.numbered => {
+                        let number = i32::from(coordinates.y - self.min_y)
+                            * i32::from(self.width())
+                            + i32::from(coordinates.x - self.min_x)
+                            + 1;
+                        format!("{: ^1$}", fit(number.to_string()), cell_size)
+                    }
+                    None => format!("{: ^1$}", fit(coordinates.to_string()), cell_size),
+                })
+                .collect()
+        });
+
+        Itertools::intersperse(rows, line_split)
+            .map(|row: Vec<String>| row.join(&options.column_separator.to_string()))
+            .join("\n")
+    }
+
+    // Every full row, column and diagonal of the board.
+    pub fn lines(&self) -> Vec<Vec<Coordinates>> {
+        self.lines_with_direction()
+            .into_iter()
+            .map(|(_, line)| line)
+            .collect()
+    }
+
+    pub fn lines_with_direction(&self) -> Vec<(Direction, Vec<Coordinates>)> {
+        let cells = (self.min_y..=self.max_y)
+            .flat_map(|y| (self.min_x..=self.max_x).map(move |x| Coordinates { x, y }))
+            .collect::<Vec<Coordinates>>();
+
+        Direction::ALL
+            .iter()
+            .flat_map(|direction| {
+                // Lines run downwards, or rightwards for rows.
+                let (dx, dy) = match direction.delta() {
+                    (dx, dy) if dy < 0 => (-dx, -dy),
+                    delta => delta,
+                };
+                let (dx, dy) = (Coord::from(dx), Coord::from(dy));
+                cells
+                    .iter()
+                    .filter(|start| !self.on_board(&start.translate(-dx, -dy)))
+                    .map(|start| {
+                        let line = (0..)
+                            .map(|step| start.translate(dx * step, dy * step))
+                            .take_while(|coordinates| self.on_board(coordinates))
+                            .collect();
+                        (*direction, line)
+                    })
+                    .collect::<Vec<(Direction, Vec<Coordinates>)>>()
+            })
+            .collect()
+    }
+
+    pub fn winning_line(&self, coordinates: &Coordinates, goal: i8) -> Option<Vec<Coordinates>> {
+        self.winning_line_with_direction(coordinates, goal)
+            .map(|(_, line)| line)
+    }
+
+    // Also tells along which direction the line runs, e.g. to strike it through.
+    pub fn winning_line_with_direction(
+        &self,
+        coordinates: &Coordinates,
+        goal: i8,
+    ) -> Option<(Direction, Vec<Coordinates>)> {
+        let player = self.hash.get(coordinates)?;
+        self.affected_rows_with_direction(coordinates, goal)
+            .into_iter()
+            .find_map(|(direction, row)| {
+                let length = self.goal_along(direction, goal).max(1) as usize;
+                (0..(row.len() + 1).saturating_sub(length))
+                    .filter(|start| row[*start..*start + length].contains(coordinates))
+                    .find(|start| self.is_run(&row, *start, length, player))
+                    .map(|start| (direction, row[start..start + length].to_vec()))
+            })
+    }
+
+    // Whether `player` holds the `length` cells of `row` from `start`. Forbidding
+    // overlines, the cells on either side mustn't be theirs.
+    fn is_run(&self, row: &[Coordinates], start: usize, length: usize, player: &Player) -> bool {
+        let owned = |index: usize| {
+            row.get(index)
+                .is_some_and(|coordinates| self.hash.get(coordinates) == Some(player))
+        };
+
+        (start..start + length).all(owned)
+            && (self.overline == OverlineRule::Allowed
+                || !(start.checked_sub(1).is_some_and(owned) || owned(start + length)))
+    }
+
+    // The most pieces `player` has in a row along any direction, whatever the goal.
+    pub fn longest_run(&self, player: &Player) -> u8 {
+        self.lines()
+            .iter()
+            .flat_map(|line| {
+                line.iter().scan(0, |run, coordinates| {
+                    *run = if self.get(coordinates) == Some(player) {
+                        *run + 1
+                    } else {
+                        0
+                    };
+                    Some(*run)
+                })
+            })
+            .max()
+            .map_or(0, |run: usize| u8::try_from(run).unwrap_or(u8::MAX))
+    }
+
+    // Scans cells by y then x, so a board with lines for both players reports the same one.
+    pub fn winner(&self, goal: i8) -> Option<Player> {
+        self.hash
+            .iter()
+            .sorted_by_key(|(coordinates, _)| (coordinates.y, coordinates.x))
+            .find(|(coordinates, _)| self.is_winning_move(coordinates, goal))
+            .map(|(_, player)| player.clone())
+    }
+
+    pub fn is_winning_move(&self, coordinates: &Coordinates, goal: i8) -> bool {
+        self.winning_line(coordinates, goal).is_some()
+    }
+
+    fn transform<F: Fn(&Coordinates) -> Coordinates>(&self, f: F) -> Board {
+        Board {
+            hash: self
+                .hash
+                .iter()
+                .map(|(coordinates, player)| (f(coordinates), player.clone()))
+                .collect(),
+            history: self
+                .history
+                .iter()
+                .map(|(coordinates, player)| (f(coordinates), player.clone()))
+                .collect(),
+            blocked: self.blocked.iter().map(&f).collect(),
+            ..self.clone()
+        }
+    }
+
+    fn is_square(&self) -> bool {
+        self.width() == self.height()
+    }
+
+    // Quarter turn clockwise, only square boards keep their bounds.
+    pub fn rotate90(&self) -> Result<Board, String> {
+        if self.is_square() {
+            Ok(self.rotate())
+        } else {
+            Err("NotSquare".to_string())
+        }
+    }
+
+    fn rotate(&self) -> Board {
+        self.transform(|c| Coordinates {
+            x: self.min_x + self.max_y - c.y,
+            y: self.min_y + c.x - self.min_x,
+        })
+    }
+
+    // Swaps the left and right sides, any board keeps its bounds.
+    pub fn mirror_horizontal(&self) -> Board {
+        self.transform(|c| Coordinates {
+            x: self.min_x + self.max_x - c.x,
+            y: c.y,
+        })
+    }
+
+    // Orders boards by their cells row by row, then by their moves.
+    fn key(&self) -> (Vec<u8>, Vec<(Coord, Coord, u8)>) {
+        let code = |player: Option<&Player>| match player {
+            None => 0,
+            Some(Player::X) => 1,
+            Some(Player::O) => 2,
+        };
+        let cells = self
+            .cells()
+            .map(|(coordinates, player)| {
+                if self.is_blocked(&coordinates) {
+                    3
+                } else {
+                    code(player)
+                }
+            })
+            .collect();
+        let moves = self
+            .history
+            .iter()
+            .map(|(coordinates, player)| (coordinates.x, coordinates.y, code(Some(player))))
+            .collect();
+
+        (cells, moves)
+    }
+
+    fn flip(&self) -> Board {
+        self.transform(|c| Coordinates {
+            x: c.x,
+            y: self.min_y + self.max_y - c.y,
+        })
+    }
+
+    // The smallest of the board's rotations and reflections. Non square boards
+    // only consider the symmetries keeping their bounds.
+    pub fn canonical(&self) -> Board {
+        let mut candidates = vec![self.clone(), self.flip()];
+        if self.is_square() {
+            for i in 2..8 {
+                let rotated = candidates[i - 2].rotate();
+                candidates.push(rotated);
+            }
+        } else {
+            candidates.push(self.mirror_horizontal());
+            candidates.push(self.mirror_horizontal().flip());
+        }
+
+        candidates
+            .into_iter()
+            .min_by_key(Board::key)
+            .unwrap_or_else(|| self.clone())
+    }
+}
+
+// Boards are stored as their bounds and moves, replayed on load to rebuild the cells.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct BoardData {
+    min_x: Coord,
+    max_x: Coord,
+    min_y: Coord,
+    max_y: Coord,
+    goal: i8,
+    #[serde(default)]
+    diagonal_goal: Option<i8>,
+    #[serde(default)]
+    overline: OverlineRule,
+    gravity: bool,
+    #[serde(default)]
+    gravity_direction: GravityDirection,
+    #[serde(default)]
+    misere: bool,
+    #[serde(default)]
+    max_pieces_per_player: Option<u8>,
+    symbols: [char; 2],
+    #[serde(default)]
+    blocked: Vec<Coordinates>,
+    moves: Vec<(Coordinates, Player)>,
+}
+
+#[cfg(feature = "serde")]
+impl From<Board> for BoardData {
+    fn from(board: Board) -> BoardData {
+        BoardData {
+            min_x: board.min_x,
+            max_x: board.max_x,
+            min_y: board.min_y,
+            max_y: board.max_y,
+            goal: board.goal,
+            diagonal_goal: board.diagonal_goal,
+            overline: board.overline,
+            gravity: board.gravity,
+            gravity_direction: board.gravity_direction,
+            misere: board.misere,
+            max_pieces_per_player: board.max_pieces_per_player,
+            symbols: board.symbols,
+            blocked: board
+                .blocked
+                .iter()
+                .cloned()
+                .sorted_by_key(|coordinates| (coordinates.y, coordinates.x))
+                .collect(),
+            moves: board.moves(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<BoardData> for Board {
+    type Error = String;
+
+    fn try_from(data: BoardData) -> Result<Board, String> {
+        let board = Board::try_new(&Game {
+            min_x: data.min_x,
+            max_x: data.max_x,
+            min_y: data.min_y,
+            max_y: data.max_y,
+            goal: data.goal,
+            diagonal_goal: data.diagonal_goal,
+            overline: data.overline,
+            gravity: data.gravity,
+            gravity_direction: data.gravity_direction,
+            misere: data.misere,
+            max_pieces_per_player: data.max_pieces_per_player,
+            symbols: data.symbols,
+        })?;
+        let board = data
+            .blocked
+            .iter()
+            .try_fold(board, |board, coordinates| board.block(coordinates))?;
+
+        data.moves
+            .iter()
+            .try_fold(board, |board, (coordinates, player)| {
+                board.insert(coordinates, player)
+            })
+            .map_err(String::from)
+    }
+}
+
+// Parses rows of `X`, `O` and `.` cells, the goal being the shorter side.
+// What `to_json` writes.
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+struct BoardJson {
+    min_x: Coord,
+    max_x: Coord,
+    min_y: Coord,
+    max_y: Coord,
+    goal: i8,
+    cells: Vec<CellJson>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    blocked: Vec<Coordinates>,
+}
+
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+struct CellJson {
+    x: Coord,
+    y: Coord,
+    player: Player,
+}
+
+#[cfg(not(feature = "serde"))]
+static OBJECT: OnceLock<Regex> = OnceLock::new();
+#[cfg(not(feature = "serde"))]
+static FIELD: OnceLock<Regex> = OnceLock::new();
+
+impl BoardJson {
+    #[cfg(feature = "serde")]
+    fn parse(s: &str) -> Option<BoardJson> {
+        serde_json::from_str(s).ok()
+    }
+
+    // Without serde, only the flat objects `to_json` writes are read, whatever the
+    // order of their keys. Cells are the inner objects with a player.
+    #[cfg(not(feature = "serde"))]
+    fn parse(s: &str) -> Option<BoardJson> {
+        let object = OBJECT.get_or_init(|| Regex::new(r"\{([^{}]*)\}").unwrap());
+        let fields = |s: &str| {
+            FIELD
+                .get_or_init(|| Regex::new(r#""(\w+)"\s*:\s*(?:(-?[0-9]+)|"([^"]*)")"#).unwrap())
+                .captures_iter(s)
+                .filter_map(|cap| {
+                    let value = cap.get(2).or_else(|| cap.get(3))?;
+                    Some((cap[1].to_string(), value.as_str().to_string()))
+                })
+                .collect::<HashMap<String, String>>()
+        };
+        fn number<A: FromStr>(fields: &HashMap<String, String>, name: &str) -> Option<A> {
+            fields.get(name)?.parse().ok()
+        }
+
+        let inner = object
+            .captures_iter(s)
+            .map(|cap| fields(&cap[1]))
+            .collect::<Vec<HashMap<String, String>>>();
+        let coordinates = |fields: &HashMap<String, String>| {
+            Some(Coordinates {
+                x: number(fields, "x")?,
+                y: number(fields, "y")?,
+            })
+        };
+        let top = fields(&object.replace_all(s, ""));
+
+        Some(BoardJson {
+            min_x: number(&top, "min_x")?,
+            max_x: number(&top, "max_x")?,
+            min_y: number(&top, "min_y")?,
+            max_y: number(&top, "max_y")?,
+            goal: number(&top, "goal")?,
+            cells: inner
+                .iter()
+                .filter(|fields| fields.contains_key("player"))
+                .map(|fields| {
+                    let mut player = fields.get("player")?.chars();
+                    let coordinates = coordinates(fields)?;
+                    match (player.next().and_then(Player::from_char), player.next()) {
+                        (Some(player), None) => Some(CellJson {
+                            x: coordinates.x,
+                            y: coordinates.y,
+                            player,
+                        }),
+                        _ => None,
+                    }
+                })
+                .collect::<Option<Vec<CellJson>>>()?,
+            blocked: inner
+                .iter()
+                .filter(|fields| !fields.contains_key("player"))
+                .map(coordinates)
+                .collect::<Option<Vec<Coordinates>>>()?,
+        })
+    }
+}
+
+impl FromStr for Board {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows = s
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<&str>>();
+        let width = rows.first().map_or(0, |row| row.chars().count());
+
+        if rows.is_empty() || width == 0 {
+            return Err("Board is empty".to_string());
+        } else if rows.iter().any(|row| row.chars().count() != width) {
+            return Err("Board rows have different lengths".to_string());
+        }
+
+        let (width, height) = match (Coord::try_from(width), Coord::try_from(rows.len())) {
+            (Ok(width), Ok(height)) => (width, height),
+            _ => return Err("Board is too large".to_string()),
+        };
+        let goal = i8::try_from(width.min(height)).unwrap_or(i8::MAX);
+        let game = Game::with_size(width, height, goal);
+
+        rows.iter()
+            .zip(game.min_y..)
+            .flat_map(|(row, y)| row.chars().zip(game.min_x..).map(move |(c, x)| (c, x, y)))
+            .try_fold(Board::new(&game), |board, (c, x, y)| match c {
+                '.' => Ok(board),
+                _ => Player::from_char(c)
+                    .ok_or_else(|| format!("Board cell '{}' can't be parsed", c))
+                    .and_then(|player| {
+                        board
+                            .insert(&Coordinates { x, y }, &player)
+                            .map_err(String::from)
+                    }),
+            })
+    }
+}
+
+impl PartialEq for Board {
+    fn eq(&self, other: &Board) -> bool {
+        self.hash == other.hash
+            && self.blocked == other.blocked
+            && self.bounds() == other.bounds()
+            && self.goal == other.goal
+            && self.diagonal_goal == other.diagonal_goal
+            && self.overline == other.overline
+            && self.gravity == other.gravity
+            && self.gravity_direction == other.gravity_direction
+            && self.misere == other.misere
+            && self.max_pieces_per_player == other.max_pieces_per_player
+            && self.symbols == other.symbols
+    }
+}
+
+impl Eq for Board {}
+
+// Each map iterates in its own order, so entries are hashed one by one and
+// combined with a commutative sum.
+fn unordered_hash<T: Hash>(items: impl Iterator<Item = T>) -> u64 {
+    items
+        .map(|item| {
+            let mut hasher = DefaultHasher::new();
+            item.hash(&mut hasher);
+            hasher.finish()
+        })
+        .fold(0, u64::wrapping_add)
+}
+
+impl Hash for Board {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        unordered_hash(self.hash.iter()).hash(state);
+        unordered_hash(self.blocked.iter()).hash(state);
+        self.bounds().hash(state);
+        self.goal.hash(state);
+        self.diagonal_goal.hash(state);
+        self.overline.hash(state);
+        self.gravity.hash(state);
+        self.gravity_direction.hash(state);
+        self.misere.hash(state);
+        self.max_pieces_per_player.hash(state);
+        self.symbols.hash(state);
+    }
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.render(&RenderOptions::default()))
+    }
+}
+
+// The compact grid, short enough for failed assertions and logs.
+impl fmt::Debug for Board {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_string_compact())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison, clippy::useless_vec)]
+mod tests {
+    use super::*;
+    use crate::coordinates::cell_number_to_coordinates;
+
+    #[test]
+    fn test_new_tic_tac_toe() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game);
+
+        assert_eq!(board.hash.len(), 0);
+        assert_eq!(board.min_x, game.min_x);
+        assert_eq!(board.max_x, game.max_x);
+        assert_eq!(board.min_y, game.min_y);
+        assert_eq!(board.max_y, game.max_y);
+        assert_eq!(board.goal, game.goal);
+        assert_eq!(board.gravity, game.gravity);
+        assert_eq!(board.symbols, game.symbols);
+    }
+
+    #[test]
+    fn test_new_gomoku() {
+        let game = Game::GOMOKU;
+        let board = Board::new(&game);
+
+        assert_eq!(board.hash.len(), 0);
+        assert_eq!(board.min_x, game.min_x);
+        assert_eq!(board.max_x, game.max_x);
+        assert_eq!(board.min_y, game.min_y);
+        assert_eq!(board.max_y, game.max_y);
+        assert_eq!(board.goal, game.goal);
+        assert_eq!(board.gravity, game.gravity);
+        assert_eq!(board.symbols, game.symbols);
+    }
+
+    #[test]
+    fn test_insert_smaller_min_x() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game);
+        let coordinates = Coordinates {
+            x: board.min_x - 1,
+            y: 0,
+        };
+        let player = Player::X;
+        let board = board.insert(&coordinates, &player);
+
+        assert_eq!(board.err(), Some(MoveError::OutOfBounds));
+    }
+
+    #[test]
+    fn test_insert_smaller_min_y() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game);
+        let coordinates = Coordinates {
+            x: 0,
+            y: board.min_y - 1,
+        };
+        let player = Player::X;
+        let board = board.insert(&coordinates, &player);
+
+        assert_eq!(board.err(), Some(MoveError::OutOfBounds));
+    }
+
+    #[test]
+    fn test_insert_bigger_max_x() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game);
+        let coordinates = Coordinates {
+            x: board.max_x + 1,
+            y: 0,
+        };
+        let player = Player::X;
+        let board = board.insert(&coordinates, &player);
+
+        assert_eq!(board.err(), Some(MoveError::OutOfBounds));
+    }
+
+    #[test]
+    fn test_bounds() {
+        assert_eq!(Board::new(&Game::TIC_TAC_TOE).bounds(), (-1, 1, -1, 1));
+        assert_eq!(
+            Board::new(&Game::with_size(7, 6, 4)).bounds(),
+            (-3, 3, -2, 3)
+        );
+    }
+
+    #[test]
+    fn test_width_height() {
+        let board = Board::new(&Game::TIC_TAC_TOE);
+        assert_eq!((board.width(), board.height()), (3, 3));
+
+        let board = Board::new(&Game::GOMOKU);
+        assert_eq!((board.width(), board.height()), (15, 15));
+
+        let board = Board::new(&Game::CONNECT_FOUR);
+        assert_eq!((board.width(), board.height()), (7, 6));
+    }
+
+    // Any Coord parses, so the board bounds are what keeps moves on the board.
+    #[test]
+    fn test_insert_parsed_off_board() {
+        let game = Game::TIC_TAC_TOE;
+        let coordinates = Coordinates::parse("300,0", &game).unwrap();
+        let board = Board::new(&game).insert(&coordinates, &Player::X);
+
+        assert_eq!(board.err(), Some(MoveError::OutOfBounds));
+    }
+
+    #[test]
+    fn test_insert_bigger_max_y() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game);
+        let coordinates = Coordinates {
+            x: 0,
+            y: board.max_y + 1,
+        };
+        let player = Player::X;
+        let board = board.insert(&coordinates, &player);
+
+        assert_eq!(board.err(), Some(MoveError::OutOfBounds));
+    }
+
+    #[test]
+    fn test_insert() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game);
+        let coordinates = Coordinates { x: 0, y: 0 };
+        let player = Player::X;
+        let board = board.insert(&coordinates, &player);
+
+        assert_eq!(board.unwrap().hash.get(&coordinates), Some(&player));
+    }
+
+    #[test]
+    fn test_insert_keeps_snapshots() {
+        let game = Game::GOMOKU;
+        let cells = (-7..=7)
+            .flat_map(|y| (-7..=7).map(move |x| Coordinates { x, y }))
+            .collect::<Vec<Coordinates>>();
+        let snapshots = cells
+            .iter()
+            .fold(vec![Board::new(&game)], |mut snapshots, c| {
+                let board = snapshots.last().unwrap().insert(c, &Player::X).unwrap();
+                snapshots.push(board);
+                snapshots
+            });
+
+        for (size, board) in snapshots.iter().enumerate() {
+            assert_eq!(board.occupied(), size);
+            assert_eq!(board.moves().len(), size);
+            assert!(cells[..size].iter().all(|c| board.get(c).is_some()));
+            assert!(cells[size..].iter().all(|c| board.get(c).is_none()));
+        }
+    }
+
+    #[test]
+    fn test_rotate() {
+        let board = Board::from_str("XO.\n...\n...").unwrap();
+
+        assert_eq!(
+            board.rotate().to_string(),
+            Board::from_str("..X\n..O\n...").unwrap().to_string()
+        );
+        assert!(board.rotate().rotate().rotate().rotate() == board);
+    }
+
+    #[test]
+    fn test_rotate90_keeps_outcome() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::from_str("XXX\nOO.\n...").unwrap();
+        let diagonal = Board::from_str("X.O\n.XO\n..X").unwrap();
+        let draw = Board::from_str("XOX\nXOO\nOXX").unwrap();
+
+        let mut rotated = board.clone();
+        for _ in 0..4 {
+            rotated = rotated.rotate90().unwrap();
+            assert_eq!(rotated.winner(game.goal), Some(Player::X));
+        }
+        assert!(rotated == board);
+        assert_eq!(
+            diagonal.rotate90().unwrap().winner(game.goal),
+            Some(Player::X)
+        );
+        assert!(draw.rotate90().unwrap().is_draw());
+        assert_eq!(draw.rotate90().unwrap().winner(game.goal), None);
+    }
+
+    #[test]
+    fn test_rotate90_not_square() {
+        let board = Board::new(&Game::CONNECT_FOUR);
+
+        assert_eq!(board.rotate90().err(), Some("NotSquare".to_string()));
+    }
+
+    #[test]
+    fn test_mirror_horizontal_keeps_outcome() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::from_str("X.O\n.XO\n..X").unwrap();
+        let mirrored = board.mirror_horizontal();
+
+        assert_eq!(mirrored.to_string_compact(), "O.X\nOX.\nX..");
+        assert_eq!(mirrored.winner(game.goal), Some(Player::X));
+        assert!(mirrored.mirror_horizontal() == board);
+
+        let connect_four =
+            Board::from_str(".......\n.......\n.......\nX......\nX......\nXO.O.O.").unwrap();
+        assert_eq!(
+            connect_four
+                .mirror_horizontal()
+                .get(&Coordinates { x: 3, y: 3 }),
+            Some(&Player::X)
+        );
+    }
+
+    #[test]
+    fn test_canonical() {
+        let board = Board::new(&Game::TIC_TAC_TOE)
+            .insert(&Coordinates { x: -1, y: -1 }, &Player::X)
+            .and_then(|board| board.insert(&Coordinates { x: 0, y: -1 }, &Player::O))
+            .and_then(|board| board.insert(&Coordinates { x: 1, y: 1 }, &Player::X))
+            .unwrap();
+        let canonical = board.canonical();
+
+        assert!(board.rotate().canonical() == canonical);
+        assert!(board.rotate().rotate().canonical() == canonical);
+        assert!(board.mirror_horizontal().canonical() == canonical);
+        assert!(canonical.canonical() == canonical);
+        assert!(
+            board
+                .insert(&Coordinates { x: 0, y: 0 }, &Player::O)
+                .unwrap()
+                .canonical()
+                != canonical
+        );
+    }
+
+    #[test]
+    fn test_canonical_not_square() {
+        let board = Board::new(&Game::CONNECT_FOUR)
+            .insert(&Coordinates { x: -3, y: 3 }, &Player::X)
+            .unwrap();
+        let canonical = board.canonical();
+
+        assert!(board.mirror_horizontal().canonical() == canonical);
+        assert!(board.flip().canonical() == canonical);
+        assert_eq!(canonical.moves().len(), 1);
+    }
+
+    #[test]
+    fn test_moves() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: 1, y: 1 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: -1, y: 0 }, &Player::O)
+            .unwrap()
+            .insert(&Coordinates { x: 0, y: -1 }, &Player::X)
+            .unwrap();
+
+        assert_eq!(
+            board.moves(),
+            &[
+                (Coordinates { x: 1, y: 1 }, Player::X),
+                (Coordinates { x: -1, y: 0 }, Player::O),
+                (Coordinates { x: 0, y: -1 }, Player::X),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_insert_already_taken() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game);
+        let coordinates = Coordinates { x: 0, y: 0 };
+        let player = Player::X;
+        let board = board
+            .insert(&coordinates, &player)
+            .and_then(|board| board.insert(&coordinates, &player));
+
+        assert_eq!(board.err(), Some(MoveError::AlreadyDefined));
+    }
+
+    #[test]
+    fn test_insert_gravity() {
+        let game = Game::CONNECT_FOUR;
+        let coordinates = Coordinates { x: 0, y: -2 };
+        let board = Board::new(&game)
+            .insert(&coordinates, &Player::X)
+            .unwrap()
+            .insert(&coordinates, &Player::O)
+            .unwrap()
+            .insert(&coordinates, &Player::X)
+            .unwrap();
+
+        assert_eq!(board.get(&Coordinates { x: 0, y: 3 }), Some(&Player::X));
+        assert_eq!(board.get(&Coordinates { x: 0, y: 2 }), Some(&Player::O));
+        assert_eq!(board.get(&Coordinates { x: 0, y: 1 }), Some(&Player::X));
+        assert_eq!(board.get(&coordinates), None);
+        assert_eq!(board.resolve(&coordinates), Ok(Coordinates { x: 0, y: 0 }));
+    }
+
+    #[test]
+    fn test_insert_gravity_left() {
+        let game = Game {
+            gravity_direction: GravityDirection::Left,
+            ..Game::CONNECT_FOUR
+        };
+        let coordinates = Coordinates { x: 2, y: 1 };
+        let board = Board::new(&game)
+            .insert(&coordinates, &Player::X)
+            .unwrap()
+            .insert(&coordinates, &Player::O)
+            .unwrap();
+
+        assert_eq!(board.get(&Coordinates { x: -3, y: 1 }), Some(&Player::X));
+        assert_eq!(board.get(&Coordinates { x: -2, y: 1 }), Some(&Player::O));
+        assert_eq!(
+            board.insert(&Coordinates { x: 0, y: 4 }, &Player::X).err(),
+            Some(MoveError::OutOfBounds)
+        );
+    }
+
+    #[test]
+    fn test_insert_gravity_up() {
+        let game = Game {
+            gravity_direction: GravityDirection::Up,
+            ..Game::CONNECT_FOUR
+        };
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: 1, y: 3 }, &Player::X)
+            .unwrap();
+
+        assert_eq!(board.get(&Coordinates { x: 1, y: -2 }), Some(&Player::X));
+        assert_eq!(board.available_moves().len(), 7);
+        assert!(board
+            .available_moves()
+            .contains(&Coordinates { x: 1, y: -1 }));
+    }
+
+    #[test]
+    fn test_insert_gravity_full_column() {
+        let game = Game::CONNECT_FOUR;
+        let coordinates = Coordinates { x: 3, y: 0 };
+        let board = (0..6).fold(Board::new(&game), |board, _| {
+            board.insert(&coordinates, &Player::X).unwrap()
+        });
+
+        assert_eq!(
+            board.insert(&coordinates, &Player::O).err(),
+            Some(MoveError::ColumnFull)
+        );
+        assert!(board
+            .insert(&Coordinates { x: 2, y: 0 }, &Player::O)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_insert_gravity_out_of_bounds() {
+        let game = Game::CONNECT_FOUR;
+        let board = Board::new(&game);
+
+        assert_eq!(
+            board.insert(&Coordinates { x: 4, y: 0 }, &Player::X).err(),
+            Some(MoveError::OutOfBounds)
+        );
+    }
+
+    #[test]
+    fn test_drop() {
+        let game = Game::CONNECT_FOUR;
+        let (board, rows) = (0..4).fold((Board::new(&game), vec![]), |(board, mut rows), _| {
+            let (board, coordinates) = board.drop(-1, &Player::X).unwrap();
+            rows.push(coordinates.y);
+            (board, rows)
+        });
+
+        assert_eq!(rows, vec![3, 2, 1, 0]);
+        assert!(board.is_winning_move(&Coordinates { x: -1, y: 0 }, game.goal));
+    }
+
+    #[test]
+    fn test_drop_gravity_up() {
+        let game = Game {
+            gravity: true,
+            gravity_direction: GravityDirection::Up,
+            ..Game::TIC_TAC_TOE
+        };
+        let (board, coordinates) = Board::new(&game).drop(1, &Player::X).unwrap();
+
+        assert_eq!(coordinates, Coordinates { x: 1, y: 1 });
+        assert_eq!(board.get(&coordinates), Some(&Player::X));
+        assert_eq!(board.occupied(), 1);
+    }
+
+    #[test]
+    fn test_drop_gravity_left() {
+        let game = Game {
+            gravity: true,
+            gravity_direction: GravityDirection::Left,
+            ..Game::TIC_TAC_TOE
+        };
+        let (board, coordinates) = Board::new(&game).drop(1, &Player::O).unwrap();
+        let (board, above) = board.drop(1, &Player::X).unwrap();
+
+        assert_eq!(coordinates, Coordinates { x: 1, y: 1 });
+        assert_eq!(above, Coordinates { x: 1, y: 0 });
+        assert_eq!(board.get(&coordinates), Some(&Player::O));
+        assert_eq!(board.get(&above), Some(&Player::X));
+    }
+
+    #[test]
+    fn test_drop_without_gravity() {
+        let game = Game::TIC_TAC_TOE;
+        let (board, coordinates) = Board::new(&game).drop(0, &Player::O).unwrap();
+
+        assert_eq!(coordinates, Coordinates { x: 0, y: 1 });
+        assert_eq!(board.get(&coordinates), Some(&Player::O));
+    }
+
+    #[test]
+    fn test_drop_full_column() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game);
+        let board = (0..3).fold(board, |board, _| board.drop(1, &Player::X).unwrap().0);
+
+        assert_eq!(board.drop(1, &Player::O).err(), Some(MoveError::ColumnFull));
+        assert_eq!(
+            board.drop(2, &Player::O).err(),
+            Some(MoveError::OutOfBounds)
+        );
+    }
+
+    #[test]
+    fn test_remove() {
+        let game = Game::TIC_TAC_TOE;
+        let coordinates = Coordinates { x: 0, y: 0 };
+        let board = Board::new(&game)
+            .insert(&coordinates, &Player::X)
+            .and_then(|board| board.remove(&coordinates));
+
+        let board = board.unwrap();
+        assert!(board.hash.is_empty());
+        assert!(board.moves().is_empty());
+    }
+
+    #[test]
+    fn test_insert_max_pieces() {
+        let game = Game {
+            max_pieces_per_player: Some(2),
+            ..Game::TIC_TAC_TOE
+        };
+        let board = [(-1, -1), (1, 1), (0, -1)]
+            .iter()
+            .try_fold(Board::new(&game), |board, (x, y)| {
+                board.insert(&Coordinates { x: *x, y: *y }, &Player::X)
+            })
+            .and_then(|board| board.insert(&Coordinates { x: 0, y: 0 }, &Player::O))
+            .unwrap();
+        let (next, vacated) = board
+            .place(&Coordinates { x: 1, y: -1 }, &Player::X)
+            .unwrap();
+
+        assert_eq!(board.get(&Coordinates { x: -1, y: -1 }), None);
+        assert_eq!(board.count(&Player::X), 2);
+        assert_eq!(vacated, Some(Coordinates { x: 1, y: 1 }));
+        assert_eq!(next.count(&Player::X), 2);
+        assert_eq!(next.get(&Coordinates { x: 0, y: 0 }), Some(&Player::O));
+        assert_eq!(
+            next.moves(),
+            vec![
+                (Coordinates { x: 0, y: -1 }, Player::X),
+                (Coordinates { x: 0, y: 0 }, Player::O),
+                (Coordinates { x: 1, y: -1 }, Player::X),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_place_under_limit() {
+        let board = Board::new(&Game::TIC_TAC_TOE);
+
+        assert_eq!(
+            board
+                .place(&Coordinates { x: 0, y: 0 }, &Player::X)
+                .map(|(_, vacated)| vacated),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn test_remove_empty() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game).remove(&Coordinates { x: 0, y: 0 });
+
+        assert_eq!(board.err(), Some(MoveError::NotDefined));
+    }
+
+    #[test]
+    fn test_overline_forbidden() {
+        let game = Game {
+            overline: OverlineRule::Forbidden,
+            ..Game::GOMOKU
+        };
+        let row = |board: Board, xs: std::ops::RangeInclusive<Coord>| {
+            xs.fold(board, |board, x| {
+                board.insert(&Coordinates { x, y: 0 }, &Player::X).unwrap()
+            })
+        };
+        let five = row(Board::new(&game), 0..=4);
+        let six = row(five.clone(), 5..=5);
+
+        assert!(five.is_winning_move(&Coordinates { x: 4, y: 0 }, game.goal));
+        assert!(!six.is_winning_move(&Coordinates { x: 5, y: 0 }, game.goal));
+        assert!(!six.is_winning_move(&Coordinates { x: 2, y: 0 }, game.goal));
+        assert_eq!(six.winner(game.goal), None);
+
+        let allowed = row(Board::new(&Game::GOMOKU), 0..=5);
+        assert!(allowed.is_winning_move(&Coordinates { x: 5, y: 0 }, game.goal));
+    }
+
+    #[test]
+    fn test_longest_run() {
+        let board = Board::from_str("X.O\n.XO\n...").unwrap();
+
+        assert_eq!(board.longest_run(&Player::X), 2);
+        assert_eq!(board.longest_run(&Player::O), 2);
+        assert_eq!(
+            Board::from_str("X.X\n...\n.X.")
+                .unwrap()
+                .longest_run(&Player::X),
+            1
+        );
+        assert_eq!(Board::new(&Game::TIC_TAC_TOE).longest_run(&Player::O), 0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let game = Game::CONNECT_FOUR;
+        let board = Board::new(&game)
+            .block(&Coordinates { x: 0, y: 0 })
+            .and_then(|board| board.insert(&Coordinates { x: 1, y: 0 }, &Player::X))
+            .unwrap();
+        let reset = board.reset();
+
+        assert!(reset.is_empty());
+        assert!(reset.moves().is_empty());
+        assert_eq!(reset.bounds(), board.bounds());
+        assert!(reset.is_blocked(&Coordinates { x: 0, y: 0 }));
+        assert!(
+            reset
+                == Board::new(&game)
+                    .block(&Coordinates { x: 0, y: 0 })
+                    .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_is_empty() {
+        let board = Board::new(&Game::TIC_TAC_TOE);
+        let coordinates = Coordinates { x: 0, y: 0 };
+
+        assert!(board.is_empty());
+        assert!(!board.insert(&coordinates, &Player::X).unwrap().is_empty());
+        assert!(board.block(&coordinates).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_occupied_cells() {
+        let board = [(-1, -1), (0, 0), (1, -1)]
+            .iter()
+            .try_fold(Board::new(&Game::GOMOKU), |board, (x, y)| {
+                board.insert(&Coordinates { x: *x, y: *y }, &Player::X)
+            })
+            .unwrap();
+        let board = board
+            .insert(&Coordinates { x: 7, y: 7 }, &Player::O)
+            .unwrap();
+
+        assert_eq!(board.occupied_cells().count(), 4);
+        assert!(board
+            .occupied_cells()
+            .any(
+                |(coordinates, player)| *coordinates == Coordinates { x: 7, y: 7 }
+                    && *player == Player::O
+            ));
+    }
+
+    #[test]
+    fn test_get() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: 0, y: 0 }, &Player::X)
+            .unwrap();
+
+        assert_eq!(board.get(&Coordinates { x: 0, y: 0 }), Some(&Player::X));
+        assert_eq!(board.get(&Coordinates { x: 1, y: 0 }), None);
+    }
+
+    #[test]
+    fn test_cells() {
+        let board = Board::from_str("X..\n.O.\n...").unwrap();
+        let cells = board
+            .cells()
+            .collect::<Vec<(Coordinates, Option<&Player>)>>();
+        let expected = (-1..=1)
+            .flat_map(|y| (-1..=1).map(move |x| Coordinates { x, y }))
+            .collect::<Vec<Coordinates>>();
+
+        assert_eq!(cells.len(), 9);
+        assert_eq!(
+            cells
+                .iter()
+                .map(|(c, _)| c.clone())
+                .collect::<Vec<Coordinates>>(),
+            expected
+        );
+        assert_eq!(cells[0].1, Some(&Player::X));
+        assert_eq!(cells[4].1, Some(&Player::O));
+        assert_eq!(cells.iter().filter(|(_, p)| p.is_none()).count(), 7);
+    }
+
+    #[test]
+    fn test_try_new() {
+        let inverted = Game {
+            min_x: 1,
+            max_x: -1,
+            ..Game::TIC_TAC_TOE
+        };
+        let unreachable = Game {
+            goal: 4,
+            ..Game::TIC_TAC_TOE
+        };
+
+        assert!(Board::try_new(&Game::TIC_TAC_TOE).is_ok());
+        assert!(Board::try_new(&inverted).is_err());
+        assert!(Board::try_new(&unreachable).is_err());
+    }
+
+    #[test]
+    fn test_goal_larger_than_board() {
+        let game = Game {
+            goal: 10,
+            ..Game::TIC_TAC_TOE
+        };
+        let board = Board::from_str("XXX\nOO.\n...").unwrap();
+        let coordinates = Coordinates { x: 1, y: -1 };
+
+        assert!(Board::try_new(&game).is_err());
+        assert!(!board.is_winning_move(&coordinates, game.goal));
+        assert_eq!(board.winning_line(&coordinates, game.goal), None);
+        assert!(board.threats(&Player::O, game.goal).is_empty());
+        assert_eq!(
+            board.outcome(game.goal),
+            Outcome::Draw(DrawReason::NoWinningLinePossible)
+        );
+    }
+
+    #[test]
+    fn test_count() {
+        let board = Board::from_str("XO.\n.X.\nO.X").unwrap();
+
+        assert_eq!(board.count(&Player::X), 3);
+        assert_eq!(board.count(&Player::O), 2);
+        assert_eq!(board.occupied(), 5);
+        assert_eq!(Board::new(&Game::TIC_TAC_TOE).occupied(), 0);
+    }
+
+    #[test]
+    fn test_get_out_of_bounds() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game);
+
+        assert_eq!(board.get(&Coordinates { x: 2, y: 0 }), None);
+        assert_eq!(board.get(&Coordinates { x: 0, y: -5 }), None);
+    }
+
+    #[test]
+    fn test_empty_cells() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game);
+        let empty_cells = board.empty_cells();
+
+        assert_eq!(empty_cells.len(), 9);
+        assert_eq!(empty_cells[0], Coordinates { x: -1, y: -1 });
+        assert_eq!(empty_cells[1], Coordinates { x: 0, y: -1 });
+
+        let board = board
+            .insert(&Coordinates { x: 0, y: 0 }, &Player::X)
+            .unwrap();
+        let empty_cells = board.empty_cells();
+
+        assert_eq!(empty_cells.len(), 8);
+        assert!(!empty_cells.contains(&Coordinates { x: 0, y: 0 }));
+    }
+
+    #[test]
+    fn test_available_moves() {
+        let board = Board::from_str("X.O\n.X.\nO.X").unwrap();
+
+        assert_eq!(
+            board.available_moves(),
+            vec![
+                Coordinates { x: 0, y: -1 },
+                Coordinates { x: -1, y: 0 },
+                Coordinates { x: 1, y: 0 },
+                Coordinates { x: 0, y: 1 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_available_moves_full() {
+        let board = Board::from_str("XOX\nXOO\nOXX").unwrap();
+
+        assert!(board.is_draw());
+        assert!(board.available_moves().is_empty());
+    }
+
+    #[test]
+    fn test_available_moves_gravity() {
+        let game = Game::CONNECT_FOUR;
+        let board = Board::new(&game).drop(0, &Player::X).unwrap().0;
+        let moves = board.available_moves();
+
+        assert_eq!(moves.len(), 7);
+        assert!(moves.contains(&Coordinates { x: 0, y: 2 }));
+        assert!(moves.contains(&Coordinates { x: 1, y: 3 }));
+    }
+
+    #[test]
+    fn test_is_forced_draw() {
+        let board = Board::from_str("XOX\nOX.\nOXO").unwrap();
+
+        assert!(!board.is_draw());
+        assert!(board.is_forced_draw(3));
+        assert!(!board.is_forced_draw(2));
+    }
+
+    #[test]
+    fn test_is_forced_draw_open_line() {
+        let board = Board::from_str("XOX\n.X.\nOXO").unwrap();
+
+        assert!(!board.is_forced_draw(3));
+        assert!(!Board::new(&Game::TIC_TAC_TOE).is_forced_draw(3));
+    }
+
+    #[test]
+    fn test_threats() {
+        let board = Board::from_str("XX.\nO..\nO..").unwrap();
+
+        assert_eq!(
+            board.threats(&Player::X, 3),
+            vec![Coordinates { x: 1, y: -1 }]
+        );
+        assert!(board.threats(&Player::O, 3).is_empty());
+    }
+
+    #[test]
+    fn test_debug() {
+        let board = Board::from_str("X..\n.O.\n...").unwrap();
+
+        assert_eq!(format!("{:?}", board), "X..\n.O.\n...");
+    }
+
+    #[test]
+    fn test_move_analysis() {
+        let board = Board::from_str("XX.\nOO.\n...").unwrap();
+        let analysis = board.move_analysis(&Player::X, 3);
+        let kind = |x, y| {
+            analysis
+                .iter()
+                .find(|(coordinates, _)| *coordinates == Coordinates { x, y })
+                .map(|(_, kind)| *kind)
+        };
+
+        assert_eq!(analysis.len(), 5);
+        assert_eq!(kind(1, -1), Some(MoveKind::Winning));
+        assert_eq!(kind(1, 0), Some(MoveKind::Blocks));
+        assert_eq!(kind(0, 1), Some(MoveKind::Neutral));
+        assert_eq!(kind(0, 0), None);
+    }
+
+    #[test]
+    fn test_threats_gravity() {
+        let game = Game::CONNECT_FOUR;
+        let board = [-3, -2, -1].iter().fold(Board::new(&game), |board, x| {
+            board.drop(*x, &Player::X).unwrap().0
+        });
+
+        assert_eq!(
+            board.threats(&Player::X, game.goal),
+            vec![Coordinates { x: 0, y: 3 }]
+        );
+    }
+
+    #[test]
+    fn test_outcome() {
+        assert_eq!(Board::new(&Game::TIC_TAC_TOE).outcome(3), Outcome::Ongoing);
+        assert_eq!(
+            Board::from_str("XO.\n.X.\n...").unwrap().outcome(3),
+            Outcome::Ongoing
+        );
+        assert_eq!(
+            Board::from_str("XOX\nXOO\nOXX").unwrap().outcome(3),
+            Outcome::Draw(DrawReason::BoardFull)
+        );
+        assert_eq!(
+            Board::from_str("XOX\nOX.\nOXO").unwrap().outcome(3),
+            Outcome::Draw(DrawReason::NoWinningLinePossible)
+        );
+    }
+
+    #[test]
+    fn test_draw_reason() {
+        assert_eq!(Board::new(&Game::TIC_TAC_TOE).draw_reason(3), None);
+        assert_eq!(
+            Board::from_str("XOX\nXOO\nOXX").unwrap().draw_reason(3),
+            Some(DrawReason::BoardFull)
+        );
+        assert_eq!(
+            Board::from_str("XOX\nOX.\nOXO").unwrap().draw_reason(3),
+            Some(DrawReason::NoWinningLinePossible)
+        );
+    }
+
+    #[test]
+    fn test_draw_reason_piece_limit() {
+        let game = Game {
+            max_pieces_per_player: Some(4),
+            ..Game::TIC_TAC_TOE
+        };
+        let board = [
+            (-1, -1),
+            (0, -1),
+            (1, -1),
+            (-1, 0),
+            (0, 0),
+            (-1, 1),
+            (0, 1),
+            (1, 1),
+        ]
+        .iter()
+        .zip([Player::X, Player::O].iter().cycle())
+        .try_fold(Board::new(&game), |board, ((x, y), player)| {
+            board.insert(&Coordinates { x: *x, y: *y }, player)
+        })
+        .unwrap();
+
+        assert_eq!(board.to_string_compact(), "XOX\nOX.\nOXO");
+        assert!(board.is_forced_draw(3));
+        assert_eq!(board.draw_reason(3), None);
+    }
+
+    #[test]
+    fn test_outcome_full_board_won() {
+        let board = Board::from_str("XOX\nOXO\nOXX").unwrap();
+
+        assert!(board.is_draw());
+        assert_eq!(board.outcome(3), Outcome::Won(Player::X));
+    }
+
+    #[test]
+    fn test_outcome_misere() {
+        let game = Game {
+            misere: true,
+            ..Game::TIC_TAC_TOE
+        };
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: -1, y: -1 }, &Player::X)
+            .and_then(|board| board.insert(&Coordinates { x: 0, y: -1 }, &Player::X))
+            .and_then(|board| board.insert(&Coordinates { x: 1, y: -1 }, &Player::X))
+            .unwrap();
+
+        assert_eq!(board.winner(game.goal), Some(Player::X));
+        assert_eq!(board.outcome(game.goal), Outcome::Won(Player::O));
+    }
+
+    #[test]
+    fn test_block() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game)
+            .block(&Coordinates { x: 0, y: -1 })
+            .unwrap();
+
+        assert!(board.is_blocked(&Coordinates { x: 0, y: -1 }));
+        assert_eq!(
+            board.insert(&Coordinates { x: 0, y: -1 }, &Player::X).err(),
+            Some(MoveError::AlreadyDefined)
+        );
+        assert_eq!(
+            board.block(&Coordinates { x: 0, y: -1 }).err(),
+            Some(MoveError::AlreadyDefined)
+        );
+        assert_eq!(
+            board.block(&Coordinates { x: 2, y: 0 }).err(),
+            Some(MoveError::OutOfBounds)
+        );
+        assert_eq!(board.empty_cells().len(), 8);
+        assert_eq!(board.to_string_compact(), ".#.\n...\n...");
+    }
+
+    #[test]
+    fn test_block_breaks_line() {
+        let game = Game::GOMOKU;
+        let board = Board::new(&game)
+            .block(&Coordinates { x: 0, y: 0 })
+            .unwrap();
+        let board = [-2, -1, 1, 2, 3].iter().fold(board, |board, x| {
+            board
+                .insert(&Coordinates { x: *x, y: 0 }, &Player::X)
+                .unwrap()
+        });
+        let last = Coordinates { x: 3, y: 0 };
+
+        assert!(!board.is_winning_move(&last, game.goal));
+        assert_eq!(board.winner(game.goal), None);
+
+        let game = Game {
+            goal: 3,
+            ..Game::TIC_TAC_TOE
+        };
+        let board = Board::new(&game)
+            .block(&Coordinates { x: 0, y: 0 })
+            .and_then(|board| board.insert(&Coordinates { x: -1, y: 0 }, &Player::X))
+            .and_then(|board| board.insert(&Coordinates { x: 1, y: 0 }, &Player::X))
+            .unwrap();
+        assert!(board.threats(&Player::X, game.goal).is_empty());
+    }
+
+    #[test]
+    fn test_block_is_draw() {
+        let board = Board::from_str("XOX\nXO.\nOXO").unwrap();
+        let blocked = board.block(&Coordinates { x: 1, y: 0 }).unwrap();
+
+        assert!(!board.is_draw());
+        assert!(blocked.is_draw());
+    }
+
+    #[test]
+    fn test_block_gravity_is_draw() {
+        let game = Game {
+            gravity: true,
+            ..Game::with_size(2, 2, 2)
+        };
+        let board = Board::new(&game)
+            .block(&Coordinates { x: 0, y: 0 })
+            .unwrap()
+            .insert(&Coordinates { x: 1, y: 0 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 1, y: 0 }, &Player::O)
+            .unwrap();
+
+        assert!(board.get(&Coordinates { x: 0, y: 1 }).is_none());
+        assert!(board.is_draw());
+    }
+
+    #[test]
+    fn test_block_gravity() {
+        let game = Game::CONNECT_FOUR;
+        let board = Board::new(&game)
+            .block(&Coordinates { x: 0, y: 1 })
+            .unwrap();
+
+        assert_eq!(
+            board.drop(0, &Player::X).unwrap().1,
+            Coordinates { x: 0, y: 0 }
+        );
+        assert_eq!(
+            board.drop(1, &Player::X).unwrap().1,
+            Coordinates { x: 1, y: 3 }
+        );
+    }
+
+    #[test]
+    fn test_is_draw_empty() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game);
+
+        assert_eq!(board.is_draw(), false);
+    }
+
+    #[test]
+    fn test_is_draw_minus_1() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: -1, y: -1 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 0, y: -1 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 1, y: -1 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: -1, y: 0 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 0, y: 0 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 1, y: 0 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: -1, y: 1 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 0, y: 1 }, &Player::X)
+            .unwrap();
+
+        assert_eq!(board.is_draw(), false);
+    }
+
+    #[test]
+    fn test_is_draw_full() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: -1, y: -1 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 0, y: -1 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 1, y: -1 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: -1, y: 0 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 0, y: 0 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 1, y: 0 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: -1, y: 1 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 0, y: 1 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 1, y: 1 }, &Player::X)
+            .unwrap();
+
+        assert_eq!(board.is_draw(), true);
+    }
+
+    #[test]
+    fn test_affected_rows_tic_tac_toe_center() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game);
+        let affected_rows = board.affected_rows(&Coordinates { x: 0, y: 0 });
+
+        assert_eq!(affected_rows.len(), 4);
+        assert!(affected_rows.contains(&vec![
+            Coordinates { x: -1, y: 0 },
+            Coordinates { x: 0, y: 0 },
+            Coordinates { x: 1, y: 0 }
+        ]));
+        assert!(affected_rows.contains(&vec![
+            Coordinates { x: 0, y: -1 },
+            Coordinates { x: 0, y: 0 },
+            Coordinates { x: 0, y: 1 }
+        ]));
+        assert!(affected_rows.contains(&vec![
+            Coordinates { x: -1, y: -1 },
+            Coordinates { x: 0, y: 0 },
+            Coordinates { x: 1, y: 1 }
+        ]));
+        assert!(affected_rows.contains(&vec![
+            Coordinates { x: -1, y: 1 },
+            Coordinates { x: 0, y: 0 },
+            Coordinates { x: 1, y: -1 }
+        ]));
+    }
+
+    #[test]
+    fn test_affected_rows_tic_tac_toe_corner() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game);
+        let affected_rows = board.affected_rows(&Coordinates { x: -1, y: -1 });
+
+        assert_eq!(affected_rows.len(), 3);
+        assert!(affected_rows.contains(&vec![
+            Coordinates { x: -1, y: -1 },
+            Coordinates { x: 0, y: -1 },
+            Coordinates { x: 1, y: -1 }
+        ]));
+        assert!(affected_rows.contains(&vec![
+            Coordinates { x: -1, y: -1 },
+            Coordinates { x: -1, y: 0 },
+            Coordinates { x: -1, y: 1 }
+        ]));
+        assert!(affected_rows.contains(&vec![
+            Coordinates { x: -1, y: -1 },
+            Coordinates { x: 0, y: 0 },
+            Coordinates { x: 1, y: 1 }
+        ]));
+    }
+
+    #[test]
+    fn test_affected_rows_tic_tac_toe_middle() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game);
+        let affected_rows = board.affected_rows(&Coordinates { x: -1, y: 0 });
+
+        assert_eq!(affected_rows.len(), 2);
+        assert!(affected_rows.contains(&vec![
+            Coordinates { x: -1, y: -1 },
+            Coordinates { x: -1, y: 0 },
+            Coordinates { x: -1, y: 1 }
+        ]));
+        assert!(affected_rows.contains(&vec![
+            Coordinates { x: -1, y: 0 },
+            Coordinates { x: 0, y: 0 },
+            Coordinates { x: 1, y: 0 }
+        ]));
+    }
+
+    #[test]
+    fn test_to_json() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: 1, y: 1 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 0, y: -1 }, &Player::O)
+            .unwrap()
+            .insert(&Coordinates { x: -1, y: 1 }, &Player::X)
+            .unwrap();
+        let expected = [
+            r#"{"min_x":-1,"max_x":1,"min_y":-1,"max_y":1,"goal":3,"cells":["#,
+            r#"{"x":0,"y":-1,"player":"O"},"#,
+            r#"{"x":-1,"y":1,"player":"X"},"#,
+            r#"{"x":1,"y":1,"player":"X"}]}"#,
+        ]
+        .concat();
+
+        assert_eq!(board.to_json(), expected);
+    }
+
+    #[test]
+    fn test_from_json_round_trip() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: -1, y: -1 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 0, y: 0 }, &Player::O)
+            .unwrap()
+            .insert(&Coordinates { x: 1, y: -1 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 0, y: -1 }, &Player::O)
+            .unwrap();
+        let parsed = Board::from_json(&board.to_json(), &game).unwrap();
+
+        assert_eq!(parsed.hash, board.hash);
+        assert_eq!(parsed.to_json(), board.to_json());
+    }
+
+    #[test]
+    fn test_from_json_round_trip_connect_four() {
+        let game = Game::CONNECT_FOUR;
+        let board = [0, 0, 1, 0, -1]
+            .iter()
+            .zip([Player::X, Player::O].iter().cycle())
+            .try_fold(Board::new(&game), |board, (x, player)| {
+                board.insert(
+                    &Coordinates {
+                        x: *x,
+                        y: game.min_y,
+                    },
+                    player,
+                )
+            })
+            .unwrap();
+        let parsed = Board::from_json(&board.to_json(), &game).unwrap();
+
+        assert_eq!(parsed.hash, board.hash);
+        assert_eq!(parsed.to_json(), board.to_json());
+    }
+
+    #[test]
+    fn test_from_json_round_trip_blocked() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game)
+            .block(&Coordinates { x: 0, y: 0 })
+            .unwrap()
+            .insert(&Coordinates { x: 1, y: -1 }, &Player::X)
+            .unwrap();
+        let parsed = Board::from_json(&board.to_json(), &game).unwrap();
+
+        assert!(board.to_json().contains(r#""blocked":[{"x":0,"y":0}]"#));
+        assert_eq!(parsed.blocked, board.blocked);
+        assert_eq!(parsed.hash, board.hash);
+    }
+
+    #[test]
+    fn test_from_json_key_order() {
+        let game = Game::TIC_TAC_TOE;
+        let json = [
+            r#"{"cells":[{"player":"O","y":-1,"x":1}],"blocked":[{"y":0,"x":0}],"#,
+            r#""goal":3,"max_y":1,"min_y":-1,"max_x":1,"min_x":-1}"#,
+        ]
+        .concat();
+        let board = Board::from_json(&json, &game).unwrap();
+
+        assert_eq!(board.get(&Coordinates { x: 1, y: -1 }), Some(&Player::O));
+        assert!(board.is_blocked(&Coordinates { x: 0, y: 0 }));
+        assert_eq!(board.occupied(), 1);
+    }
+
+    #[test]
+    fn test_from_json_out_of_bounds() {
+        let game = Game::TIC_TAC_TOE;
+        let json = r#"{"min_x":-1,"max_x":1,"min_y":-1,"max_y":1,"goal":3,"cells":[{"x":2,"y":0,"player":"X"}]}"#;
+
+        assert!(Board::from_json(json, &game).is_err());
+    }
+
+    #[test]
+    fn test_from_json_other_game() {
+        let board = Board::new(&Game::GOMOKU);
+
+        assert!(Board::from_json(&board.to_json(), &Game::TIC_TAC_TOE).is_err());
+    }
+
+    #[test]
+    fn test_lines_tic_tac_toe() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game);
+        let lines = board.lines();
+
+        assert_eq!(lines.iter().filter(|line| line.len() == 3).count(), 8);
+        assert!(lines.contains(&vec![
+            Coordinates { x: -1, y: 0 },
+            Coordinates { x: 0, y: 0 },
+            Coordinates { x: 1, y: 0 }
+        ]));
+        assert!(lines.contains(&vec![
+            Coordinates { x: 1, y: -1 },
+            Coordinates { x: 0, y: 0 },
+            Coordinates { x: -1, y: 1 }
+        ]));
+        assert!(lines.contains(&vec![
+            Coordinates { x: 0, y: -1 },
+            Coordinates { x: 1, y: 0 }
+        ]));
+    }
+
+    #[test]
+    fn test_is_winning_move_missing() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: -1, y: -1 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 0, y: -1 }, &Player::X)
+            .unwrap();
+
+        assert_eq!(
+            board.is_winning_move(&Coordinates { x: 1, y: -1 }, 3),
+            false
+        );
+    }
+
+    #[test]
+    fn test_is_winning_move_blocked() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: -1, y: -1 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 0, y: -1 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 1, y: -1 }, &Player::O)
+            .unwrap();
+
+        assert_eq!(
+            board.is_winning_move(&Coordinates { x: 1, y: -1 }, 3),
+            false
+        );
+    }
+
+    #[test]
+    fn test_is_winning_move() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: -1, y: -1 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 0, y: -1 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 1, y: -1 }, &Player::X)
+            .unwrap();
+
+        assert_eq!(board.is_winning_move(&Coordinates { x: 1, y: -1 }, 3), true);
+    }
+
+    #[test]
+    fn test_is_winning_move_shorter_goal() {
+        let game = Game::with_size(5, 3, 5);
+        let board = (-1..=1).fold(Board::new(&game), |board, y| {
+            board.insert(&Coordinates { x: 0, y }, &Player::X).unwrap()
+        });
+
+        assert!(board.is_winning_move(&Coordinates { x: 0, y: 1 }, 3));
+        assert!(!board.is_winning_move(&Coordinates { x: 0, y: 1 }, game.goal));
+    }
+
+    #[test]
+    fn test_from_str() {
+        let board = Board::from_str(
+            "
+            X.O
+            .X.
+            O..
+            ",
+        )
+        .unwrap();
+        let expected = Board::new(&Game::TIC_TAC_TOE)
+            .insert(&Coordinates { x: -1, y: -1 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 1, y: -1 }, &Player::O)
+            .unwrap()
+            .insert(&Coordinates { x: 0, y: 0 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: -1, y: 1 }, &Player::O)
+            .unwrap();
+
+        assert!(board == expected);
+    }
+
+    #[test]
+    fn test_from_str_uneven_rows() {
+        assert!(Board::from_str("X.O\n.X\nO..").is_err());
+    }
+
+    #[test]
+    fn test_from_str_unknown_cell() {
+        assert!(Board::from_str("X.O\n.Z.\nO..").is_err());
+    }
+
+    #[test]
+    fn test_from_str_empty() {
+        assert!(Board::from_str("\n\n").is_err());
+    }
+
+    #[test]
+    fn test_is_winning_move_off_center_diagonal() {
+        let game = Game::with_size(7, 6, 4);
+        let board = (0..4).fold(Board::new(&game), |board, d| {
+            board
+                .insert(&Coordinates { x: d, y: 3 - d }, &Player::O)
+                .unwrap()
+        });
+
+        assert!(board.is_winning_move(&Coordinates { x: 3, y: 0 }, game.goal));
+    }
+
+    #[test]
+    fn test_is_winning_move_large_board() {
+        let game = Game::with_size(40, 40, 5);
+        let board = (16..=20).fold(Board::new(&game), |board, x| {
+            board.insert(&Coordinates { x, y: 20 }, &Player::X).unwrap()
+        });
+
+        assert_eq!((board.max_x, board.max_y), (20, 20));
+        assert!(board.is_winning_move(&Coordinates { x: 20, y: 20 }, game.goal));
+        assert!(!board.is_winning_move(&Coordinates { x: 20, y: 19 }, game.goal));
+    }
+
+    #[test]
+    fn test_winning_line() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::from_str("O.X\nOX.\nX..").unwrap();
+        let line = board
+            .winning_line(&Coordinates { x: 0, y: 0 }, game.goal)
+            .unwrap();
+
+        assert_eq!(line.len(), game.goal as usize);
+        assert!(line
+            .iter()
+            .all(|coordinates| board.get(coordinates) == Some(&Player::X)));
+        assert!(line.contains(&Coordinates { x: 1, y: -1 }));
+        assert!(line.contains(&Coordinates { x: -1, y: 1 }));
+    }
+
+    #[test]
+    fn test_winning_line_none() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::from_str("O.X\nOX.\n...").unwrap();
+
+        assert_eq!(
+            board.winning_line(&Coordinates { x: 0, y: 0 }, game.goal),
+            None
+        );
+    }
+
+    #[test]
+    fn test_winner() {
+        let game = Game::GOMOKU;
+        let board = (-7..=-3).fold(Board::new(&game), |board, x| {
+            board.insert(&Coordinates { x, y: -7 }, &Player::O).unwrap()
+        });
+        let last = Coordinates { x: 7, y: 7 };
+        let board = board
+            .insert(&Coordinates { x: 6, y: 7 }, &Player::X)
+            .unwrap()
+            .insert(&last, &Player::X)
+            .unwrap();
+
+        assert!(!board.is_winning_move(&last, game.goal));
+        assert_eq!(board.winner(game.goal), Some(Player::O));
+    }
+
+    #[test]
+    fn test_winner_none() {
+        let board = Board::from_str("XOX\nXOO\nOXX").unwrap();
+
+        assert_eq!(board.winner(3), None);
+        assert_eq!(Board::new(&Game::TIC_TAC_TOE).winner(3), None);
+    }
+
+    #[test]
+    fn test_diagonal_goal() {
+        let game = Game {
+            diagonal_goal: Some(3),
+            ..Game::with_size(4, 4, 4)
+        };
+        let board = [(-1, -1), (0, 0), (1, 1)]
+            .iter()
+            .fold(Board::new(&game), |board, &(x, y)| {
+                board.insert(&Coordinates { x, y }, &Player::X).unwrap()
+            });
+        assert!(board.is_winning_move(&Coordinates { x: 1, y: 1 }, game.goal));
+        assert_eq!(
+            board
+                .winning_line_with_direction(&Coordinates { x: 1, y: 1 }, game.goal)
+                .map(|(direction, _)| direction),
+            Some(Direction::Diagonal)
+        );
+
+        let board = [(-1, 2), (0, 2), (1, 2)]
+            .iter()
+            .fold(Board::new(&game), |board, &(x, y)| {
+                board.insert(&Coordinates { x, y }, &Player::O).unwrap()
+            });
+        assert!(!board.is_winning_move(&Coordinates { x: 1, y: 2 }, game.goal));
+        assert!(board
+            .insert(&Coordinates { x: 2, y: 2 }, &Player::O)
+            .unwrap()
+            .is_winning_move(&Coordinates { x: 2, y: 2 }, game.goal));
+    }
+
+    #[test]
+    fn test_diagonal_goal_forced_draw() {
+        // Both rows are blocked and the columns are too short, but diagonals of two remain open.
+        let played = |game: &Game| {
+            [
+                (-1, 0, Player::X),
+                (0, 0, Player::O),
+                (1, 0, Player::X),
+                (-1, 1, Player::X),
+                (0, 1, Player::O),
+            ]
+            .iter()
+            .fold(Board::new(game), |board, (x, y, player)| {
+                board.insert(&Coordinates { x: *x, y: *y }, player).unwrap()
+            })
+        };
+        let game = Game::with_size(3, 2, 3);
+        let diagonal = Game {
+            diagonal_goal: Some(2),
+            ..game
+        };
+
+        assert!(played(&game).is_forced_draw(game.goal));
+        assert!(!played(&diagonal).is_forced_draw(diagonal.goal));
+    }
+
+    #[test]
+    fn test_winning_line_with_direction() {
+        let board = Board::from_str("O.X\nOX.\nX..").unwrap();
+
+        assert_eq!(
+            board.winning_line_with_direction(&Coordinates { x: 0, y: 0 }, 3),
+            Some((
+                Direction::AntiDiagonal,
+                vec![
+                    Coordinates { x: -1, y: 1 },
+                    Coordinates { x: 0, y: 0 },
+                    Coordinates { x: 1, y: -1 },
+                ]
+            ))
+        );
+
+        let board = Board::from_str("X.O\n.XO\n..X").unwrap();
+        assert_eq!(
+            board
+                .winning_line_with_direction(&Coordinates { x: 1, y: 1 }, 3)
+                .map(|(direction, _)| direction),
+            Some(Direction::Diagonal)
+        );
+
+        let board = Board::from_str("XXX\nO.O\n...").unwrap();
+        assert_eq!(
+            board
+                .winning_line_with_direction(&Coordinates { x: 0, y: -1 }, 3)
+                .map(|(direction, _)| direction),
+            Some(Direction::Horizontal)
+        );
+    }
+
+    #[test]
+    fn test_to_string_highlighted() {
+        let board = Board::from_str("O.X\nOX.\nX..").unwrap();
+        let line = board.winning_line(&Coordinates { x: 0, y: 0 }, 3).unwrap();
+        let expected = [
+            "   O   | 0,-1  |  [X]  ",
+            "-------|-------|-------",
+            "   O   |  [X]  |  1,0  ",
+            "-------|-------|-------",
+            "  [X]  |  0,1  |  1,1  ",
+        ]
+        .join("\n");
+
+        assert_eq!(board.to_string_highlighted(&line), expected);
+    }
+
+    #[test]
+    fn test_to_string_tic_tac_toe() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game);
+        let expected = vec![
+            " -1,-1 | 0,-1  | 1,-1  ",
+            "-------|-------|-------",
+            " -1,0  |  0,0  |  1,0  ",
+            "-------|-------|-------",
+            " -1,1  |  0,1  |  1,1  ",
+        ]
+        .join("\n");
+
+        assert_eq!(board.to_string(), expected);
+    }
+
+    #[test]
+    fn test_to_string_tic_tac_toe_played() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: 0, y: 0 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 1, y: -1 }, &Player::O)
+            .unwrap();
+        let expected = [
+            " -1,-1 | 0,-1  |   O   ",
+            "-------|-------|-------",
+            " -1,0  |   X   |  1,0  ",
+            "-------|-------|-------",
+            " -1,1  |  0,1  |  1,1  ",
+        ]
+        .join("\n");
+
+        assert_eq!(board.to_string(), expected);
+    }
+
+    fn hash_of(board: &Board) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        board.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn test_hash_ignores_move_order() {
+        let game = Game::TIC_TAC_TOE;
+        let moves = [
+            (Coordinates { x: -1, y: -1 }, Player::X),
+            (Coordinates { x: 0, y: 0 }, Player::O),
+            (Coordinates { x: 1, y: 1 }, Player::X),
+        ];
+        let forwards = moves.iter().fold(Board::new(&game), |board, (c, p)| {
+            board.insert(c, p).unwrap()
+        });
+        let backwards = moves.iter().rev().fold(Board::new(&game), |board, (c, p)| {
+            board.insert(c, p).unwrap()
+        });
+
+        assert!(forwards == backwards);
+        assert_eq!(hash_of(&forwards), hash_of(&backwards));
+        assert_ne!(forwards.moves(), backwards.moves());
+    }
+
+    #[test]
+    fn test_hash_differs() {
+        let game = Game::TIC_TAC_TOE;
+        let coordinates = Coordinates { x: 0, y: 0 };
+        let x = Board::new(&game).insert(&coordinates, &Player::X).unwrap();
+        let o = Board::new(&game).insert(&coordinates, &Player::O).unwrap();
+        let blocked = Board::new(&game).block(&coordinates).unwrap();
+
+        assert!(x != o);
+        assert_ne!(hash_of(&x), hash_of(&o));
+        assert_ne!(hash_of(&x), hash_of(&blocked));
+        assert_ne!(hash_of(&Board::new(&game)), hash_of(&blocked));
+    }
+
+    #[test]
+    fn test_grid() {
+        let board = Board::new(&Game::CONNECT_FOUR)
+            .insert(&Coordinates { x: -3, y: 0 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 3, y: 0 }, &Player::O)
+            .unwrap();
+        let grid = board.grid();
+
+        assert_eq!(grid.len(), board.height() as usize);
+        assert!(grid.iter().all(|row| row.len() == board.width() as usize));
+        assert_eq!(grid[5][0], Some(Player::X));
+        assert_eq!(grid[5][6], Some(Player::O));
+        assert_eq!(
+            grid.iter().flatten().filter(|cell| cell.is_some()).count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_render_cell_width() {
+        let board = Board::new(&Game::GOMOKU)
+            .insert(&Coordinates { x: 0, y: 0 }, &Player::X)
+            .unwrap();
+        let wide = board.to_string();
+        let narrow = board.render(&RenderOptions {
+            cell_width: Some(1),
+            ..RenderOptions::default()
+        });
+
+        assert!(narrow.len() < wide.len());
+        assert_eq!(narrow.lines().count(), wide.lines().count());
+        assert!(narrow.lines().all(|line| line.chars().count() == 29));
+        assert_eq!(
+            narrow.lines().nth(14),
+            Some(" | | | | | | |X| | | | | | | ")
+        );
+    }
+
+    #[test]
+    fn test_render_separators() {
+        let board = Board::from_str("X.\n.O").unwrap();
+        let rendered = board.render(&RenderOptions {
+            cell_width: Some(3),
+            column_separator: ' ',
+            row_separator: ' ',
+            ..RenderOptions::default()
+        });
+
+        assert_eq!(rendered, [" X  1,0", "       ", "0,1  O "].join("\n"));
+    }
+
+    #[test]
+    fn test_to_string_numbered() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game);
+        let expected = [
+            "   1   |   2   |   3   ",
+            "-------|-------|-------",
+            "   4   |   5   |   6   ",
+            "-------|-------|-------",
+            "   7   |   8   |   9   ",
+        ]
+        .join("\n");
+
+        assert_eq!(board.to_string_numbered(), expected);
+
+        let coordinates = cell_number_to_coordinates(6, &game).unwrap();
+        let board = board.insert(&coordinates, &Player::X).unwrap();
+        assert!(board.to_string_numbered().contains("   5   |   X   "));
+    }
+
+    #[test]
+    fn test_to_string_compact() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: 0, y: 0 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 1, y: -1 }, &Player::O)
+            .unwrap();
+
+        assert_eq!(board.to_string_compact(), "..O\n.X.\n...");
+        assert_eq!(
+            Board::from_str(&board.to_string_compact())
+                .unwrap()
+                .to_string(),
+            board.to_string()
+        );
+    }
+
+    #[test]
+    fn test_to_string_with_headers() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: 0, y: 0 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 1, y: -1 }, &Player::O)
+            .unwrap();
+        let expected = ["    -1  0  1", "-1         O", " 0      X", " 1"].join("\n");
+
+        assert_eq!(board.to_string_with_headers(), expected);
+    }
+
+    #[test]
+    fn test_to_string_symbols() {
+        let game = Game {
+            symbols: ['❌', '⭕'],
+            ..Game::TIC_TAC_TOE
+        };
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: 0, y: 0 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 1, y: -1 }, &Player::O)
+            .unwrap();
+        let expected = [
+            " -1,-1 | 0,-1  |   ⭕   ",
+            "-------|-------|-------",
+            " -1,0  |   ❌   |  1,0  ",
+            "-------|-------|-------",
+            " -1,1  |  0,1  |  1,1  ",
+        ]
+        .join("\n");
+
+        assert_eq!(board.to_string(), expected);
+    }
+
+    #[test]
+    fn test_to_string_colored() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: 0, y: 0 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: 1, y: -1 }, &Player::O)
+            .unwrap();
+        let x = format!("   {}X{}   ", Player::X.color(), COLOR_RESET);
+        let o = format!("   {}O{}   ", Player::O.color(), COLOR_RESET);
+        let expected = [
+            format!(" -1,-1 | 0,-1  |{}", o),
+            "-------|-------|-------".to_string(),
+            format!(" -1,0  |{}|  1,0  ", x),
+            "-------|-------|-------".to_string(),
+            " -1,1  |  0,1  |  1,1  ".to_string(),
+        ]
+        .join("\n");
+
+        assert_eq!(board.to_string_colored(), expected);
+        assert_ne!(board.to_string_colored(), board.to_string());
+    }
+
+    #[test]
+    fn test_to_string_colored_empty() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game);
+
+        assert_eq!(board.to_string_colored(), board.to_string());
+    }
+
+    #[test]
+    fn test_to_string_gomoku() {
+        let game = Game::GOMOKU;
+        let board = Board::new(&game);
+        let expected = vec!(
+            " -7,-7 | -6,-7 | -5,-7 | -4,-7 | -3,-7 | -2,-7 | -1,-7 | 0,-7  | 1,-7  | 2,-7  | 3,-7  | 4,-7  | 5,-7  | 6,-7  | 7,-7  ",
+            "-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------",
+            " -7,-6 | -6,-6 | -5,-6 | -4,-6 | -3,-6 | -2,-6 | -1,-6 | 0,-6  | 1,-6  | 2,-6  | 3,-6  | 4,-6  | 5,-6  | 6,-6  | 7,-6  ",
+            "-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------",
+            " -7,-5 | -6,-5 | -5,-5 | -4,-5 | -3,-5 | -2,-5 | -1,-5 | 0,-5  | 1,-5  | 2,-5  | 3,-5  | 4,-5  | 5,-5  | 6,-5  | 7,-5  ",
+            "-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------",
+            " -7,-4 | -6,-4 | -5,-4 | -4,-4 | -3,-4 | -2,-4 | -1,-4 | 0,-4  | 1,-4  | 2,-4  | 3,-4  | 4,-4  | 5,-4  | 6,-4  | 7,-4  ",
+            "-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------",
+            " -7,-3 | -6,-3 | -5,-3 | -4,-3 | -3,-3 | -2,-3 | -1,-3 | 0,-3  | 1,-3  | 2,-3  | 3,-3  | 4,-3  | 5,-3  | 6,-3  | 7,-3  ",
+            "-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------",
+            " -7,-2 | -6,-2 | -5,-2 | -4,-2 | -3,-2 | -2,-2 | -1,-2 | 0,-2  | 1,-2  | 2,-2  | 3,-2  | 4,-2  | 5,-2  | 6,-2  | 7,-2  ",
+            "-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------",
+            " -7,-1 | -6,-1 | -5,-1 | -4,-1 | -3,-1 | -2,-1 | -1,-1 | 0,-1  | 1,-1  | 2,-1  | 3,-1  | 4,-1  | 5,-1  | 6,-1  | 7,-1  ",
+            "-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------",
+            " -7,0  | -6,0  | -5,0  | -4,0  | -3,0  | -2,0  | -1,0  |  0,0  |  1,0  |  2,0  |  3,0  |  4,0  |  5,0  |  6,0  |  7,0  ",
+            "-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------",
+            " -7,1  | -6,1  | -5,1  | -4,1  | -3,1  | -2,1  | -1,1  |  0,1  |  1,1  |  2,1  |  3,1  |  4,1  |  5,1  |  6,1  |  7,1  ",
+            "-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------",
+            " -7,2  | -6,2  | -5,2  | -4,2  | -3,2  | -2,2  | -1,2  |  0,2  |  1,2  |  2,2  |  3,2  |  4,2  |  5,2  |  6,2  |  7,2  ",
+            "-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------",
+            " -7,3  | -6,3  | -5,3  | -4,3  | -3,3  | -2,3  | -1,3  |  0,3  |  1,3  |  2,3  |  3,3  |  4,3  |  5,3  |  6,3  |  7,3  ",
+            "-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------",
+            " -7,4  | -6,4  | -5,4  | -4,4  | -3,4  | -2,4  | -1,4  |  0,4  |  1,4  |  2,4  |  3,4  |  4,4  |  5,4  |  6,4  |  7,4  ",
+            "-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------",
+            " -7,5  | -6,5  | -5,5  | -4,5  | -3,5  | -2,5  | -1,5  |  0,5  |  1,5  |  2,5  |  3,5  |  4,5  |  5,5  |  6,5  |  7,5  ",
+            "-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------",
+            " -7,6  | -6,6  | -5,6  | -4,6  | -3,6  | -2,6  | -1,6  |  0,6  |  1,6  |  2,6  |  3,6  |  4,6  |  5,6  |  6,6  |  7,6  ",
+            "-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------|-------",
+            " -7,7  | -6,7  | -5,7  | -4,7  | -3,7  | -2,7  | -1,7  |  0,7  |  1,7  |  2,7  |  3,7  |  4,7  |  5,7  |  6,7  |  7,7  ",
+        ).join("\n");
+
+        assert_eq!(board.to_string(), expected);
+    }
+}
@@ -0,0 +1,373 @@
+use regex::Regex;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use crate::error::CoordinateParseError;
+use crate::game::Game;
+
+pub type Coord = i16;
+
+static ALGEBRAIC: OnceLock<Regex> = OnceLock::new();
+
+#[derive(PartialEq, Eq, Clone, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Coordinates {
+    pub x: Coord,
+    pub y: Coord,
+}
+
+impl Coordinates {
+    pub fn translate(&self, dx: Coord, dy: Coord) -> Coordinates {
+        Coordinates {
+            x: self.x + dx,
+            y: self.y + dy,
+        }
+    }
+
+    // The surrounding cells, row by row, whether or not they are on a board.
+    pub fn neighbors(&self) -> [Coordinates; 8] {
+        [
+            self.translate(-1, -1),
+            self.translate(0, -1),
+            self.translate(1, -1),
+            self.translate(-1, 0),
+            self.translate(1, 0),
+            self.translate(-1, 1),
+            self.translate(0, 1),
+            self.translate(1, 1),
+        ]
+    }
+
+    // Accepts `x,y`, algebraic notation, where `a1` is the `min_x`,`min_y` corner,
+    // and on square boards a cell number. Surrounding whitespace is ignored.
+    pub fn parse(s: &str, game: &Game) -> Result<Coordinates, CoordinateParseError> {
+        let s = s.trim();
+        if let Some(coordinates) = s
+            .parse()
+            .ok()
+            .and_then(|n| cell_number_to_coordinates(n, game))
+        {
+            return Ok(coordinates);
+        }
+
+        ALGEBRAIC
+            .get_or_init(|| Regex::new(r"^([a-zA-Z])([0-9]+)$").unwrap())
+            .captures(s)
+            .map(|cap| {
+                let column = cap[1].to_ascii_lowercase().as_bytes()[0] - b'a';
+                let x = i32::from(game.min_x) + i32::from(column);
+                let y = cap[2]
+                    .parse::<i32>()
+                    .ok()
+                    .map(|row| i32::from(game.min_y) + row - 1);
+
+                match (Coord::try_from(x), y.and_then(|y| Coord::try_from(y).ok())) {
+                    (Ok(x), Some(y)) => Ok(Coordinates { x, y }),
+                    _ => Err(CoordinateParseError::OutOfRange),
+                }
+            })
+            .unwrap_or_else(|| Coordinates::from_str(s))
+    }
+}
+
+// Numbers cells row by row from 1 in the `min_x`,`min_y` corner, like a phone
+// keypad on a 3x3 board. Only square boards are numbered.
+pub fn cell_number_to_coordinates(n: u8, game: &Game) -> Option<Coordinates> {
+    let width = i32::from(game.max_x) - i32::from(game.min_x) + 1;
+    let height = i32::from(game.max_y) - i32::from(game.min_y) + 1;
+    let index = i32::from(n) - 1;
+
+    if width != height || index < 0 || index >= width * height {
+        None
+    } else {
+        Some(Coordinates {
+            x: game.min_x + (index % width) as Coord,
+            y: game.min_y + (index / width) as Coord,
+        })
+    }
+}
+
+// A component is an optional `+` or `-` sign followed by digits, nothing else.
+fn component(s: &str) -> Result<Coord, CoordinateParseError> {
+    let digits = s
+        .strip_prefix('-')
+        .or_else(|| s.strip_prefix('+'))
+        .unwrap_or(s);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        Err(CoordinateParseError::NotANumber)
+    } else {
+        s.parse().map_err(|_| CoordinateParseError::OutOfRange)
+    }
+}
+
+// `x,y` where both components are signed integers, e.g. `-1,+2`. Whitespace is
+// trimmed around the whole, but not allowed around the comma or the signs.
+impl FromStr for Coordinates {
+    type Err = CoordinateParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (x, y) = s
+            .trim()
+            .split_once(',')
+            .ok_or(CoordinateParseError::MissingComma)?;
+
+        Ok(Coordinates {
+            x: component(x)?,
+            y: component(y)?,
+        })
+    }
+}
+
+impl fmt::Display for Coordinates {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            Coordinates::from_str("-1,-1").unwrap(),
+            Coordinates { x: -1, y: -1 }
+        );
+        assert_eq!(
+            Coordinates::from_str("-1,0").unwrap(),
+            Coordinates { x: -1, y: 0 }
+        );
+        assert_eq!(
+            Coordinates::from_str("-1,1").unwrap(),
+            Coordinates { x: -1, y: 1 }
+        );
+    }
+
+    #[test]
+    fn test_to_string() {
+        assert_eq!(Coordinates { x: -1, y: 0 }.to_string(), "-1,0");
+
+        for &(x, y) in [
+            (-7, -7),
+            (-1, 1),
+            (0, 0),
+            (3, -12),
+            (Coord::MAX, Coord::MIN),
+        ]
+        .iter()
+        {
+            let coordinates = Coordinates { x, y };
+            assert_eq!(
+                Coordinates::from_str(&coordinates.to_string()).unwrap(),
+                coordinates
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_str_many() {
+        for x in -100..=100 {
+            for y in -100..=100 {
+                assert_eq!(
+                    Coordinates::from_str(&format!("{},{}", x, y)),
+                    Ok(Coordinates { x, y })
+                );
+                assert_eq!(
+                    Coordinates::from_str(&format!("{};{}", x, y)),
+                    Err(CoordinateParseError::MissingComma)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_str_signs_and_whitespace() {
+        assert_eq!(
+            Coordinates::from_str("+1,+2"),
+            Ok(Coordinates { x: 1, y: 2 })
+        );
+        assert_eq!(
+            Coordinates::from_str(" 1,2 \n"),
+            Ok(Coordinates { x: 1, y: 2 })
+        );
+        assert_eq!(
+            Coordinates::from_str("1 ,2"),
+            Err(CoordinateParseError::NotANumber)
+        );
+        assert_eq!(
+            Coordinates::from_str("1, 2"),
+            Err(CoordinateParseError::NotANumber)
+        );
+        assert_eq!(
+            Coordinates::from_str("+-1,2"),
+            Err(CoordinateParseError::NotANumber)
+        );
+        assert_eq!(
+            Coordinates::parse(" b2 ", &Game::TIC_TAC_TOE),
+            Ok(Coordinates { x: 0, y: 0 })
+        );
+    }
+
+    #[test]
+    fn test_from_str_errors() {
+        assert_eq!(
+            Coordinates::from_str("1 1"),
+            Err(CoordinateParseError::MissingComma)
+        );
+        assert_eq!(
+            Coordinates::from_str(""),
+            Err(CoordinateParseError::MissingComma)
+        );
+        assert_eq!(
+            Coordinates::from_str("a,1"),
+            Err(CoordinateParseError::NotANumber)
+        );
+        assert_eq!(
+            Coordinates::from_str("1,"),
+            Err(CoordinateParseError::NotANumber)
+        );
+        assert_eq!(
+            Coordinates::from_str("1,2,3"),
+            Err(CoordinateParseError::NotANumber)
+        );
+        assert_eq!(
+            Coordinates::from_str("-,0"),
+            Err(CoordinateParseError::NotANumber)
+        );
+        assert_eq!(
+            Coordinates::from_str("40000,0"),
+            Err(CoordinateParseError::OutOfRange)
+        );
+        assert_eq!(
+            Coordinates::from_str("0,-40000"),
+            Err(CoordinateParseError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_from_str_overflow_boundary() {
+        assert_eq!(
+            Coordinates::from_str("32767,-32768"),
+            Ok(Coordinates {
+                x: Coord::MAX,
+                y: Coord::MIN
+            })
+        );
+        assert_eq!(
+            Coordinates::from_str("32768,0"),
+            Err(CoordinateParseError::OutOfRange)
+        );
+        assert_eq!(
+            Coordinates::from_str("0,-32769"),
+            Err(CoordinateParseError::OutOfRange)
+        );
+        assert_eq!(
+            Coordinates::from_str("99999999999999999999,0"),
+            Err(CoordinateParseError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_parse_algebraic_out_of_range() {
+        assert_eq!(
+            Coordinates::parse("a99999", &Game::TIC_TAC_TOE),
+            Err(CoordinateParseError::OutOfRange)
+        );
+        assert_eq!(
+            Coordinates::parse("a99999999999999999999", &Game::TIC_TAC_TOE),
+            Err(CoordinateParseError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_translate() {
+        let coordinates = Coordinates { x: 2, y: -1 };
+
+        assert_eq!(coordinates.translate(0, 0), coordinates);
+        assert_eq!(coordinates.translate(-3, -2), Coordinates { x: -1, y: -3 });
+        assert_eq!(coordinates.translate(1, 4), Coordinates { x: 3, y: 3 });
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let neighbors = Coordinates { x: 0, y: 0 }.neighbors();
+        let expected = (-1..=1)
+            .flat_map(|y| (-1..=1).map(move |x| Coordinates { x, y }))
+            .filter(|coordinates| *coordinates != Coordinates { x: 0, y: 0 })
+            .collect::<Vec<Coordinates>>();
+
+        assert_eq!(neighbors.to_vec(), expected);
+    }
+
+    #[test]
+    fn test_parse_algebraic() {
+        let game = Game::TIC_TAC_TOE;
+
+        assert_eq!(
+            Coordinates::parse("a1", &game).unwrap(),
+            Coordinates { x: -1, y: -1 }
+        );
+        assert_eq!(
+            Coordinates::parse("B2", &game).unwrap(),
+            Coordinates { x: 0, y: 0 }
+        );
+        assert_eq!(
+            Coordinates::parse("c3", &game).unwrap(),
+            Coordinates { x: 1, y: 1 }
+        );
+    }
+
+    #[test]
+    fn test_cell_number_to_coordinates() {
+        let game = Game::TIC_TAC_TOE;
+
+        assert_eq!(
+            cell_number_to_coordinates(1, &game),
+            Some(Coordinates { x: -1, y: -1 })
+        );
+        assert_eq!(
+            cell_number_to_coordinates(5, &game),
+            Some(Coordinates { x: 0, y: 0 })
+        );
+        assert_eq!(
+            cell_number_to_coordinates(9, &game),
+            Some(Coordinates { x: 1, y: 1 })
+        );
+        assert_eq!(cell_number_to_coordinates(0, &game), None);
+        assert_eq!(cell_number_to_coordinates(10, &game), None);
+        assert_eq!(cell_number_to_coordinates(1, &Game::CONNECT_FOUR), None);
+    }
+
+    #[test]
+    fn test_parse_cell_number() {
+        let game = Game::TIC_TAC_TOE;
+
+        assert_eq!(
+            Coordinates::parse("3", &game),
+            Ok(Coordinates { x: 1, y: -1 })
+        );
+        assert_eq!(
+            Coordinates::parse("10", &game),
+            Err(CoordinateParseError::MissingComma)
+        );
+    }
+
+    #[test]
+    fn test_parse_numeric() {
+        let game = Game::TIC_TAC_TOE;
+
+        assert_eq!(
+            Coordinates::parse("1,-1", &game).unwrap(),
+            Coordinates { x: 1, y: -1 }
+        );
+        assert_eq!(
+            Coordinates::parse("1;-1", &game),
+            Err(CoordinateParseError::MissingComma)
+        );
+    }
+}
@@ -0,0 +1,41 @@
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+pub enum Direction {
+    Horizontal,
+    Vertical,
+    Diagonal,
+    AntiDiagonal,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Horizontal,
+        Direction::Vertical,
+        Direction::Diagonal,
+        Direction::AntiDiagonal,
+    ];
+
+    // One step along the direction, y growing downwards.
+    pub fn delta(&self) -> (i8, i8) {
+        match self {
+            Direction::Horizontal => (1, 0),
+            Direction::Vertical => (0, 1),
+            Direction::Diagonal => (1, 1),
+            Direction::AntiDiagonal => (1, -1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delta() {
+        let deltas = Direction::ALL
+            .iter()
+            .map(Direction::delta)
+            .collect::<Vec<(i8, i8)>>();
+
+        assert_eq!(deltas, vec![(1, 0), (0, 1), (1, 1), (1, -1)]);
+    }
+}
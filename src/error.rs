@@ -0,0 +1,92 @@
+use std::error::Error;
+use std::fmt;
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum MoveError {
+    OutOfBounds,
+    AlreadyDefined,
+    ColumnFull,
+    NotDefined,
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            MoveError::OutOfBounds => "OutOfBounds",
+            MoveError::AlreadyDefined => "AlreadyDefined",
+            MoveError::ColumnFull => "ColumnFull",
+            MoveError::NotDefined => "NotDefined",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+impl Error for MoveError {}
+
+// Lets `?` keep working in the functions that still report errors as strings.
+impl From<MoveError> for String {
+    fn from(error: MoveError) -> String {
+        error.to_string()
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum CoordinateParseError {
+    MissingComma,
+    NotANumber,
+    OutOfRange,
+}
+
+impl fmt::Display for CoordinateParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let reason = match self {
+            CoordinateParseError::MissingComma => "expected x,y",
+            CoordinateParseError::NotANumber => "not a number",
+            CoordinateParseError::OutOfRange => "out of range",
+        };
+        write!(f, "Coordinates can't be parsed: {}", reason)
+    }
+}
+
+impl Error for CoordinateParseError {}
+
+impl From<CoordinateParseError> for String {
+    fn from(error: CoordinateParseError) -> String {
+        error.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(MoveError::OutOfBounds.to_string(), "OutOfBounds");
+        assert_eq!(MoveError::AlreadyDefined.to_string(), "AlreadyDefined");
+        assert_eq!(MoveError::ColumnFull.to_string(), "ColumnFull");
+        assert_eq!(MoveError::NotDefined.to_string(), "NotDefined");
+    }
+
+    #[test]
+    fn test_into_string() {
+        let error: String = MoveError::OutOfBounds.into();
+        assert_eq!(error, "OutOfBounds");
+    }
+
+    #[test]
+    fn test_coordinate_parse_error_display() {
+        assert_eq!(
+            CoordinateParseError::MissingComma.to_string(),
+            "Coordinates can't be parsed: expected x,y"
+        );
+        assert_eq!(
+            CoordinateParseError::NotANumber.to_string(),
+            "Coordinates can't be parsed: not a number"
+        );
+        assert_eq!(
+            CoordinateParseError::OutOfRange.to_string(),
+            "Coordinates can't be parsed: out of range"
+        );
+    }
+}
@@ -0,0 +1,13 @@
+use crate::coordinates::Coordinates;
+use crate::error::MoveError;
+use crate::outcome::DrawReason;
+use crate::player::Player;
+
+// What happened during a transition, for a UI to present however it likes.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum GameEvent {
+    MovePlayed(Coordinates, Player),
+    IllegalMove(MoveError),
+    Won(Player),
+    Draw(DrawReason),
+}
@@ -0,0 +1,459 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::coordinates::Coord;
+use crate::direction::Direction;
+use crate::player::Player;
+
+const SYMBOLS: [char; 2] = ['X', 'O'];
+
+// Where pieces fall to in games with gravity, y growing downwards.
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GravityDirection {
+    #[default]
+    Down,
+    Up,
+    Left,
+    Right,
+}
+
+// Whether a line longer than the goal wins, Gomoku's overline rule forbidding it.
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum OverlineRule {
+    #[default]
+    Allowed,
+    Forbidden,
+}
+
+#[cfg(feature = "serde")]
+fn default_symbols() -> [char; 2] {
+    SYMBOLS
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Game {
+    pub min_x: Coord,
+    pub max_x: Coord,
+    pub min_y: Coord,
+    pub max_y: Coord,
+
+    pub goal: i8,
+
+    // A different goal for diagonal lines, rows and columns using `goal`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub diagonal_goal: Option<i8>,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub overline: OverlineRule,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub gravity: bool,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub gravity_direction: GravityDirection,
+
+    // Completing a line loses rather than wins.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub misere: bool,
+
+    // Playing past the limit takes the player's oldest piece off the board.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_pieces_per_player: Option<u8>,
+
+    #[cfg_attr(feature = "serde", serde(default = "default_symbols"))]
+    pub symbols: [char; 2],
+}
+
+impl Game {
+    pub fn new(
+        min_x: Coord,
+        max_x: Coord,
+        min_y: Coord,
+        max_y: Coord,
+        goal: i8,
+    ) -> Result<Game, String> {
+        let game = Game {
+            min_x,
+            max_x,
+            min_y,
+            max_y,
+            goal,
+            diagonal_goal: None,
+            overline: OverlineRule::Allowed,
+            gravity: false,
+            gravity_direction: GravityDirection::Down,
+            misere: false,
+            max_pieces_per_player: None,
+            symbols: SYMBOLS,
+        };
+        game.validate().map(|_| game)
+    }
+
+    pub fn goal_along(&self, direction: Direction) -> i8 {
+        match direction {
+            Direction::Diagonal | Direction::AntiDiagonal => {
+                self.diagonal_goal.unwrap_or(self.goal)
+            }
+            Direction::Horizontal | Direction::Vertical => self.goal,
+        }
+    }
+
+    pub fn symbol(&self, player: &Player) -> char {
+        match player {
+            Player::X => self.symbols[0],
+            Player::O => self.symbols[1],
+        }
+    }
+
+    // Rejects inverted bounds and goals that can't be reached.
+    pub fn validate(&self) -> Result<(), String> {
+        let width = i32::from(self.max_x) - i32::from(self.min_x) + 1;
+        let height = i32::from(self.max_y) - i32::from(self.min_y) + 1;
+
+        if width < 1 || height < 1 {
+            Err(format!(
+                "Bounds {},{} to {},{} are inverted",
+                self.min_x, self.min_y, self.max_x, self.max_y
+            ))
+        } else if self.goal < 1 {
+            Err(format!("Goal {} must be positive", self.goal))
+        } else if let Some(goal) = self.diagonal_goal.filter(|goal| *goal < 1) {
+            Err(format!("Diagonal goal {} must be positive", goal))
+        } else if self.max_pieces_per_player == Some(0) {
+            Err("A player must be allowed at least one piece".to_string())
+        } else if i32::from(self.goal) > width.max(height) {
+            Err(format!(
+                "Goal {} can't be reached on a {}x{} board",
+                self.goal, width, height
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    // Centers the board on 0,0, an even size extends one further positively.
+    pub fn with_size(width: Coord, height: Coord, goal: i8) -> Game {
+        let min_x = -(width - 1) / 2;
+        let min_y = -(height - 1) / 2;
+
+        Game {
+            min_x,
+            max_x: min_x + width - 1,
+            min_y,
+            max_y: min_y + height - 1,
+            goal,
+            diagonal_goal: None,
+            overline: OverlineRule::Allowed,
+            gravity: false,
+            gravity_direction: GravityDirection::Down,
+            misere: false,
+            max_pieces_per_player: None,
+            symbols: SYMBOLS,
+        }
+    }
+
+    // Reads `--width`, `--height` and `--goal`. The height defaults to the width,
+    // and the goal to the shorter side.
+    pub fn from_args(args: &[String]) -> Result<Game, String> {
+        let mut width = None;
+        let mut height = None;
+        let mut goal = None;
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let value = args
+                .next()
+                .ok_or_else(|| format!("Missing value for {}", arg))?;
+            let parsed = value
+                .parse::<i8>()
+                .ok()
+                .filter(|value| *value > 0)
+                .ok_or_else(|| format!("Invalid value for {}: {}", arg, value))?;
+
+            match arg.as_str() {
+                "--width" => width = Some(Coord::from(parsed)),
+                "--height" => height = Some(Coord::from(parsed)),
+                "--goal" => goal = Some(parsed),
+                _ => return Err(format!("Unknown argument {}", arg)),
+            }
+        }
+
+        let width = width.unwrap_or(3);
+        let height = height.unwrap_or(width);
+        let goal = goal.unwrap_or_else(|| width.min(height) as i8);
+
+        let game = Game::with_size(width, height, goal);
+        game.validate().map(|_| game)
+    }
+
+    pub const PRESETS: [(&'static str, Game); 3] = [
+        ("tic-tac-toe", Game::TIC_TAC_TOE),
+        ("gomoku", Game::GOMOKU),
+        ("connect-four", Game::CONNECT_FOUR),
+    ];
+
+    pub fn preset(name: &str) -> Option<Game> {
+        Game::PRESETS
+            .iter()
+            .find(|(preset, _)| preset.eq_ignore_ascii_case(name))
+            .map(|(_, game)| *game)
+    }
+
+    // A variant shared as JSON, with the same fields as a saved game. Gravity,
+    // misère and diagonal goals default to off, symbols to X and O.
+    #[cfg(feature = "serde")]
+    pub fn from_json(s: &str) -> Result<Game, String> {
+        let game =
+            serde_json::from_str::<Game>(s).map_err(|e| format!("Game can't be parsed: {}", e))?;
+        game.validate().map(|_| game)
+    }
+
+    pub const TIC_TAC_TOE: Game = Game {
+        min_x: -1,
+        max_x: 1,
+        min_y: -1,
+        max_y: 1,
+        goal: 3,
+        diagonal_goal: None,
+        overline: OverlineRule::Allowed,
+        gravity: false,
+        gravity_direction: GravityDirection::Down,
+        misere: false,
+        max_pieces_per_player: None,
+        symbols: SYMBOLS,
+    };
+
+    pub const GOMOKU: Game = Game {
+        min_x: -7,
+        max_x: 7,
+        min_y: -7,
+        max_y: 7,
+        goal: 5,
+        diagonal_goal: None,
+        overline: OverlineRule::Allowed,
+        gravity: false,
+        gravity_direction: GravityDirection::Down,
+        misere: false,
+        max_pieces_per_player: None,
+        symbols: SYMBOLS,
+    };
+
+    pub const CONNECT_FOUR: Game = Game {
+        min_x: -3,
+        max_x: 3,
+        min_y: -2,
+        max_y: 3,
+        goal: 4,
+        diagonal_goal: None,
+        overline: OverlineRule::Allowed,
+        gravity: true,
+        gravity_direction: GravityDirection::Down,
+        misere: false,
+        max_pieces_per_player: None,
+        symbols: SYMBOLS,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let game = Game::new(-2, 2, 0, 3, 4).unwrap();
+        assert_eq!(
+            (game.min_x, game.max_x, game.min_y, game.max_y, game.goal),
+            (-2, 2, 0, 3, 4)
+        );
+    }
+
+    #[test]
+    fn test_new_inverted_x() {
+        assert!(Game::new(1, -1, -1, 1, 3).is_err());
+    }
+
+    #[test]
+    fn test_new_inverted_y() {
+        assert!(Game::new(-1, 1, 1, -1, 3).is_err());
+    }
+
+    #[test]
+    fn test_new_unreachable_goal() {
+        assert!(Game::new(-1, 1, -1, 1, 4).is_err());
+        assert!(Game::new(-1, 1, -1, 2, 4).is_ok());
+    }
+
+    #[test]
+    fn test_new_non_positive_goal() {
+        assert!(Game::new(-1, 1, -1, 1, 0).is_err());
+        assert!(Game::new(-1, 1, -1, 1, -3).is_err());
+    }
+
+    #[test]
+    fn test_validate_diagonal_goal() {
+        let game = Game {
+            diagonal_goal: Some(0),
+            ..Game::TIC_TAC_TOE
+        };
+        assert!(game.validate().is_err());
+
+        let game = Game {
+            diagonal_goal: Some(2),
+            ..Game::TIC_TAC_TOE
+        };
+        assert!(game.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_max_pieces() {
+        let game = Game {
+            max_pieces_per_player: Some(0),
+            ..Game::TIC_TAC_TOE
+        };
+
+        assert!(game.validate().is_err());
+        assert!(Game {
+            max_pieces_per_player: Some(3),
+            ..game
+        }
+        .validate()
+        .is_ok());
+    }
+
+    #[test]
+    fn test_goal_along() {
+        let game = Game {
+            diagonal_goal: Some(3),
+            ..Game::with_size(5, 5, 4)
+        };
+
+        assert_eq!(game.goal_along(Direction::Horizontal), 4);
+        assert_eq!(game.goal_along(Direction::Vertical), 4);
+        assert_eq!(game.goal_along(Direction::Diagonal), 3);
+        assert_eq!(game.goal_along(Direction::AntiDiagonal), 3);
+        assert_eq!(Game::GOMOKU.goal_along(Direction::Diagonal), 5);
+    }
+
+    #[test]
+    fn test_presets_are_valid() {
+        for game in [Game::TIC_TAC_TOE, Game::GOMOKU, Game::CONNECT_FOUR].iter() {
+            assert!(Game::new(game.min_x, game.max_x, game.min_y, game.max_y, game.goal).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_symbol() {
+        let game = Game {
+            symbols: ['❌', '⭕'],
+            ..Game::TIC_TAC_TOE
+        };
+
+        assert_eq!(Game::TIC_TAC_TOE.symbol(&Player::X), 'X');
+        assert_eq!(Game::TIC_TAC_TOE.symbol(&Player::O), 'O');
+        assert_eq!(game.symbol(&Player::X), '❌');
+        assert_eq!(game.symbol(&Player::O), '⭕');
+    }
+
+    #[test]
+    fn test_with_size() {
+        let game = Game::with_size(3, 3, 3);
+        assert_eq!(
+            (game.min_x, game.max_x, game.min_y, game.max_y, game.goal),
+            (-1, 1, -1, 1, 3)
+        );
+
+        let game = Game::with_size(7, 6, 4);
+        assert_eq!(
+            (game.min_x, game.max_x, game.min_y, game.max_y, game.goal),
+            (-3, 3, -2, 3, 4)
+        );
+    }
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_from_args_default() {
+        let game = Game::from_args(&[]).unwrap();
+        assert_eq!(
+            (game.min_x, game.max_x, game.min_y, game.max_y, game.goal),
+            (-1, 1, -1, 1, 3)
+        );
+    }
+
+    #[test]
+    fn test_from_args() {
+        let game = Game::from_args(&args(&["--width", "15", "--goal", "5"])).unwrap();
+        assert_eq!(
+            (game.min_x, game.max_x, game.min_y, game.max_y, game.goal),
+            (-7, 7, -7, 7, 5)
+        );
+
+        let game = Game::from_args(&args(&["--height", "6", "--width", "7"])).unwrap();
+        assert_eq!(
+            (game.min_x, game.max_x, game.min_y, game.max_y, game.goal),
+            (-3, 3, -2, 3, 6)
+        );
+    }
+
+    #[test]
+    fn test_from_args_unreachable_goal() {
+        assert!(Game::from_args(&args(&["--width", "3", "--goal", "4"])).is_err());
+        assert!(Game::from_args(&args(&["--width", "3", "--height", "4", "--goal", "4"])).is_ok());
+    }
+
+    #[test]
+    fn test_from_args_invalid() {
+        assert!(Game::from_args(&args(&["--width"])).is_err());
+        assert!(Game::from_args(&args(&["--width", "three"])).is_err());
+        assert!(Game::from_args(&args(&["--width", "0"])).is_err());
+        assert!(Game::from_args(&args(&["--depth", "3"])).is_err());
+    }
+
+    #[test]
+    fn test_preset() {
+        assert_eq!(Game::preset("gomoku"), Some(Game::GOMOKU));
+        assert_eq!(Game::preset("Connect-Four"), Some(Game::CONNECT_FOUR));
+        assert_eq!(Game::preset("chess"), None);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_from_json() {
+        let game = Game::from_json(
+            r#"{"min_x": 0, "max_x": 4, "min_y": 0, "max_y": 3, "goal": 4, "gravity": true, "symbols": ["R", "Y"]}"#,
+        );
+
+        assert_eq!(
+            game,
+            Ok(Game {
+                min_x: 0,
+                max_x: 4,
+                min_y: 0,
+                max_y: 3,
+                goal: 4,
+                diagonal_goal: None,
+                overline: OverlineRule::Allowed,
+                gravity: true,
+                gravity_direction: GravityDirection::Down,
+                misere: false,
+                max_pieces_per_player: None,
+                symbols: ['R', 'Y'],
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_from_json_invalid() {
+        assert!(
+            Game::from_json(r#"{"min_x": 0, "max_x": 2, "min_y": 0, "max_y": 2, "goal": 4}"#)
+                .is_err()
+        );
+        assert!(Game::from_json(r#"{"min_x": 0"#).is_err());
+    }
+}
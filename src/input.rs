@@ -0,0 +1,198 @@
+use std::collections::VecDeque;
+use std::io;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+pub trait InputSource {
+    // Fails with `TimedOut` when no line arrives before the timeout, and with
+    // `UnexpectedEof` once the input is over.
+    fn next_line(&mut self, timeout: Option<Duration>) -> io::Result<String>;
+}
+
+fn end_of_input() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::UnexpectedEof,
+        "Input can't be read: end of input",
+    )
+}
+
+// Lines are read on their own thread, the only way to stop waiting on stdin. The
+// thread keeps reading past a timeout, so lines typed before the next prompt are
+// late answers to the one that timed out and get dropped.
+pub struct Stdin {
+    lines: Receiver<io::Result<String>>,
+    timed_out: bool,
+}
+
+impl Stdin {
+    pub fn new() -> Stdin {
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || loop {
+            let mut line = String::new();
+            let read = match io::stdin().read_line(&mut line) {
+                Ok(0) => break,
+                Ok(_) => Ok(line),
+                Err(e) => Err(io::Error::new(
+                    e.kind(),
+                    format!("Input can't be read: {}", e),
+                )),
+            };
+            if sender.send(read).is_err() {
+                break;
+            }
+        });
+
+        Stdin::with_lines(lines)
+    }
+
+    fn with_lines(lines: Receiver<io::Result<String>>) -> Stdin {
+        Stdin {
+            lines,
+            timed_out: false,
+        }
+    }
+}
+
+impl Default for Stdin {
+    fn default() -> Stdin {
+        Stdin::new()
+    }
+}
+
+impl InputSource for Stdin {
+    fn next_line(&mut self, timeout: Option<Duration>) -> io::Result<String> {
+        if self.timed_out {
+            self.timed_out = false;
+            if let Some(e) = self.lines.try_iter().find_map(Result::err) {
+                return Err(e);
+            }
+        }
+        let line = match timeout {
+            Some(timeout) => self.lines.recv_timeout(timeout),
+            None => self.lines.recv().map_err(RecvTimeoutError::from),
+        };
+
+        match line {
+            Ok(line) => line,
+            Err(RecvTimeoutError::Timeout) => {
+                self.timed_out = true;
+                Err(io::ErrorKind::TimedOut.into())
+            }
+            Err(RecvTimeoutError::Disconnected) => Err(end_of_input()),
+        }
+    }
+}
+
+enum Line {
+    Answer(String),
+    TimedOut,
+    Late(String),
+}
+
+// Plays back lines in order, `None` standing for a player who doesn't answer in time.
+pub struct Scripted {
+    lines: VecDeque<Line>,
+    timed_out: bool,
+}
+
+impl Scripted {
+    pub fn new(lines: &[Option<&str>]) -> Scripted {
+        Scripted {
+            lines: VecDeque::new(),
+            timed_out: false,
+        }
+        .then(lines)
+    }
+
+    pub fn then(mut self, lines: &[Option<&str>]) -> Scripted {
+        self.lines.extend(lines.iter().map(|line| match line {
+            Some(line) => Line::Answer(line.to_string()),
+            None => Line::TimedOut,
+        }));
+        self
+    }
+
+    // A line typed before the next prompt, dropped like `Stdin` does when the prompt
+    // it answers timed out.
+    pub fn late(mut self, line: &str) -> Scripted {
+        self.lines.push_back(Line::Late(line.to_string()));
+        self
+    }
+}
+
+impl InputSource for Scripted {
+    fn next_line(&mut self, _timeout: Option<Duration>) -> io::Result<String> {
+        loop {
+            let timed_out = self.timed_out;
+            self.timed_out = false;
+            match self.lines.pop_front() {
+                Some(Line::Answer(line)) => return Ok(line),
+                Some(Line::TimedOut) => {
+                    self.timed_out = true;
+                    return Err(io::ErrorKind::TimedOut.into());
+                }
+                Some(Line::Late(_)) if timed_out => self.timed_out = true,
+                Some(Line::Late(line)) => return Ok(line),
+                None => return Err(end_of_input()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scripted() {
+        let mut input = Scripted::new(&[Some("0,0"), None]);
+
+        assert_eq!(input.next_line(None).unwrap(), "0,0");
+        assert_eq!(
+            input
+                .next_line(Some(Duration::from_secs(1)))
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::TimedOut
+        );
+        assert_eq!(
+            input.next_line(None).unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+    }
+
+    #[test]
+    fn test_scripted_late() {
+        let mut input = Scripted::new(&[None]).late("1,1").then(&[Some("0,0")]);
+
+        assert!(input.next_line(Some(Duration::from_secs(1))).is_err());
+        assert_eq!(input.next_line(None).unwrap(), "0,0");
+        assert_eq!(
+            Scripted::new(&[]).late("1,1").next_line(None).unwrap(),
+            "1,1"
+        );
+    }
+
+    #[test]
+    fn test_stdin_drops_late_lines() {
+        let (sender, lines) = mpsc::channel();
+        let mut input = Stdin::with_lines(lines);
+
+        assert_eq!(
+            input
+                .next_line(Some(Duration::from_millis(1)))
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::TimedOut
+        );
+        sender.send(Ok("1,1\n".to_string())).unwrap();
+        let answer = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            sender.send(Ok("0,0\n".to_string())).unwrap();
+        });
+
+        assert_eq!(input.next_line(None).unwrap(), "0,0\n");
+        answer.join().unwrap();
+    }
+}
@@ -0,0 +1,21 @@
+pub mod ai;
+pub mod bitboard;
+pub mod board;
+pub mod coordinates;
+pub mod direction;
+pub mod error;
+pub mod event;
+pub mod game;
+pub mod input;
+#[cfg(feature = "net")]
+pub mod net;
+pub mod outcome;
+pub mod player;
+pub mod replay;
+#[cfg(feature = "serde")]
+pub mod save;
+pub mod scoreboard;
+pub mod session;
+pub mod state;
+#[cfg(feature = "wasm")]
+pub mod wasm;
@@ -1,5 +0,0 @@
-pub mod board;
-pub mod coordinates;
-pub mod game;
-pub mod player;
-pub mod state;
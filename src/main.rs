@@ -1,42 +1,267 @@
+use std::env;
+use std::fmt;
+use std::fs;
 use std::io;
+use std::io::{IsTerminal, Write};
+#[cfg(feature = "net")]
+use std::net::{TcpListener, TcpStream};
+use std::process;
 use std::str::FromStr;
+use std::time::Duration;
 
-mod lib;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 
-use crate::lib::board::Board;
-use crate::lib::coordinates::Coordinates;
-use crate::lib::game::Game;
-use crate::lib::player::Player;
-use crate::lib::state::State;
+use tic_tac_toe::ai::{self, Difficulty};
+use tic_tac_toe::board::Board;
+use tic_tac_toe::coordinates::Coordinates;
+use tic_tac_toe::error::MoveError;
+use tic_tac_toe::event::GameEvent;
+use tic_tac_toe::game::Game;
+use tic_tac_toe::input::{InputSource, Stdin};
+#[cfg(feature = "net")]
+use tic_tac_toe::net::{self, Spectators};
+use tic_tac_toe::outcome::{DrawReason, Outcome};
+use tic_tac_toe::player::Player;
+use tic_tac_toe::replay::{import_moves, replay};
+use tic_tac_toe::scoreboard::{starting_player, Scoreboard, Series};
+use tic_tac_toe::state::State;
 
-fn start_game(game: &Game) -> State {
-    State::NextTurn(Player::first(), Board::new(game))
+enum Command {
+    Play(Coordinates),
+    Undo,
+    Hint,
+    Resign,
+    OfferDraw,
+    Quit,
 }
 
-fn next_turn(game: &Game, player: &Player, board: &Board) -> State {
-    println!("Player {:?}'s turn", player);
-    println!("{}", board.to_string());
-    println!();
-    println!("Where would you like to play ?");
-    read_input::<Coordinates>()
-        .and_then(|coordinates| {
-            board
-                .insert(&coordinates, &player)
-                .map(|board| (board, coordinates))
+// Moves that can't be played stay typed, so they can be explained to the player.
+enum TurnError {
+    Move(MoveError),
+    Other(String),
+}
+
+impl fmt::Display for TurnError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TurnError::Move(e) => write!(f, "{}", e),
+            TurnError::Other(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<String> for TurnError {
+    fn from(error: String) -> TurnError {
+        TurnError::Other(error)
+    }
+}
+
+struct Options {
+    ai: Option<Difficulty>,
+    move_timeout: Option<Duration>,
+    first: Player,
+    colored: bool,
+    verbose: bool,
+}
+
+// Like `println!`, for the output the game is played on.
+macro_rules! say {
+    ($output:expr) => {
+        writeln!($output).expect("Output can't be written")
+    };
+    ($output:expr, $($arg:tt)*) => {
+        writeln!($output, $($arg)*).expect("Output can't be written")
+    };
+}
+
+impl Command {
+    fn parse(s: &str, game: &Game) -> Result<Command, String> {
+        match s.to_lowercase().as_str() {
+            "undo" => Ok(Command::Undo),
+            "hint" => Ok(Command::Hint),
+            "resign" => Ok(Command::Resign),
+            "draw?" => Ok(Command::OfferDraw),
+            "quit" | "q" => Ok(Command::Quit),
+            _ => Coordinates::parse(s, game)
+                .map(Command::Play)
+                .map_err(String::from),
+        }
+    }
+}
+
+fn choose_difficulty(input: &mut dyn InputSource, output: &mut dyn Write) -> Option<Difficulty> {
+    say!(
+        output,
+        "Which difficulty would you like to play against (easy, medium, hard) ?"
+    );
+    say!(output, "Leave empty to play against a friend");
+    let line = read_input::<String>(input, None).unwrap_or_default();
+    if line.is_empty() {
+        None
+    } else {
+        line.parse::<Difficulty>().map(Some).unwrap_or_else(|e| {
+            say!(output, "Error: {}", e);
+            choose_difficulty(input, output)
         })
-        .map(|(new_board, coordinates)| {
-            if new_board.is_winning_move(&coordinates, game.goal) {
-                State::Won(player.clone())
-            } else if new_board.is_draw() {
-                State::Draw
-            } else {
-                State::NextTurn(player.next(), new_board)
+    }
+}
+
+// A player running out of time gets a random move rather than losing the turn.
+fn next_command(
+    game: &Game,
+    options: &Options,
+    input: &mut dyn InputSource,
+    output: &mut dyn Write,
+    rng: &mut StdRng,
+    player: &Player,
+    board: &Board,
+) -> Result<Command, String> {
+    match &options.ai {
+        Some(difficulty) if *player != Player::first() => {
+            let (coordinates, stats) = difficulty.next_move_with_stats(game, board, player, rng);
+            if let (true, Some(stats)) = (options.verbose, stats) {
+                say!(
+                    output,
+                    "Searched {} nodes, {} plies deep, in {}ms",
+                    stats.nodes,
+                    stats.depth,
+                    stats.elapsed.as_millis()
+                );
+            }
+            coordinates
+                .map(|coordinates| {
+                    say!(
+                        output,
+                        "Player {} plays {}",
+                        game.symbol(player),
+                        coordinates
+                    );
+                    Command::Play(coordinates)
+                })
+                .ok_or_else(|| "NoMoveAvailable".to_string())
+        }
+        _ => {
+            say!(output, "Where would you like to play ?");
+            if let Some(timeout) = options.move_timeout {
+                say!(output, "You have {} second(s)", timeout.as_secs());
             }
+            match read_input::<String>(input, options.move_timeout) {
+                Err(e) if e.kind() == io::ErrorKind::TimedOut => ai::random_move(board, rng)
+                    .map(|coordinates| {
+                        say!(
+                            output,
+                            "Time is up, player {} plays {}",
+                            game.symbol(player),
+                            coordinates
+                        );
+                        Command::Play(coordinates)
+                    })
+                    .ok_or_else(|| "NoMoveAvailable".to_string()),
+                line => line
+                    .map_err(|e| e.to_string())
+                    .and_then(|line| Command::parse(&line, game)),
+            }
+        }
+    }
+}
+
+fn play(
+    game: &Game,
+    output: &mut dyn Write,
+    player: &Player,
+    board: &Board,
+    coordinates: Coordinates,
+) -> Result<State, TurnError> {
+    let (state, events) =
+        State::NextTurn(player.clone(), board.clone()).transition(game, &coordinates);
+    for event in events {
+        match event {
+            GameEvent::MovePlayed(coordinates, player) => {
+                if let (State::Won(_), Ok(board)) = (&state, board.insert(&coordinates, &player)) {
+                    let line = board
+                        .winning_line(&coordinates, game.goal)
+                        .unwrap_or_default();
+                    say!(output, "{}", board.to_string_highlighted(&line));
+                }
+            }
+            GameEvent::IllegalMove(e) => return Err(TurnError::Move(e)),
+            GameEvent::Won(_) | GameEvent::Draw(_) => (),
+        }
+    }
+
+    Ok(state)
+}
+
+// Against the AI, its reply is undone as well so that the human plays again.
+fn undo(options: &Options, board: &Board) -> Result<State, TurnError> {
+    let amount = if options.ai.is_some() { 2 } else { 1 };
+    let moves = board.moves();
+    if moves.len() < amount {
+        Err(TurnError::Other("NothingToUndo".to_string()))
+    } else {
+        let undone = &moves[moves.len() - amount..];
+        undone
+            .iter()
+            .try_fold(board.clone(), |board, (coordinates, _)| {
+                board.remove(coordinates)
+            })
+            .map_err(TurnError::Move)
+            .map(|board| State::NextTurn(undone[0].1.clone(), board))
+    }
+}
+
+fn next_turn(
+    game: &Game,
+    options: &Options,
+    input: &mut dyn InputSource,
+    output: &mut dyn Write,
+    rng: &mut StdRng,
+    player: &Player,
+    board: &Board,
+) -> State {
+    say!(output, "Player {}'s turn", game.symbol(player));
+    if options.colored {
+        say!(output, "{}", board.to_string_colored());
+    } else {
+        say!(output, "{}", board);
+    }
+    say!(output);
+    if !game.misere && (options.ai.is_none() || *player == Player::first()) {
+        for coordinates in board.threats(&player.next(), game.goal) {
+            say!(
+                output,
+                "Warning: player {} can win at {}",
+                game.symbol(&player.next()),
+                coordinates
+            );
+        }
+    }
+    next_command(game, options, input, output, rng, player, board)
+        .map_err(TurnError::from)
+        .and_then(|command| match command {
+            Command::Play(coordinates) => play(game, output, player, board, coordinates),
+            Command::Undo => undo(options, board),
+            Command::Hint => Ok(show_hint(game, output, player, board)),
+            Command::Resign => Ok(resign(game, output, player)),
+            Command::OfferDraw => Ok(offer_draw(game, options, input, output, player, board)),
+            Command::Quit => Ok(quit(output)),
         })
         .unwrap_or_else(|e| {
-            println!("Error: {}", e);
-            println!("Try again ?");
-            if read_input::<bool>().unwrap_or(false) {
+            say!(output, "Error: {}", e);
+            if let TurnError::Move(MoveError::OutOfBounds) = e {
+                let (min_x, max_x, min_y, max_y) = board.bounds();
+                say!(
+                    output,
+                    "x goes from {} to {} and y from {} to {}",
+                    min_x,
+                    max_x,
+                    min_y,
+                    max_y
+                );
+            }
+            say!(output, "Try again (y/n) ?");
+            if read_yes_no(input) {
                 State::NextTurn(player.clone(), board.clone())
             } else {
                 State::EndGame
@@ -44,49 +269,857 @@ fn next_turn(game: &Game, player: &Player, board: &Board) -> State {
         })
 }
 
-fn draw() -> State {
-    println!("Game finished with a draw");
+// Showing a hint doesn't use up the turn.
+fn show_hint(game: &Game, output: &mut dyn Write, player: &Player, board: &Board) -> State {
+    match ai::hint(game, board, player) {
+        Some(coordinates) => say!(output, "Hint: play {}", coordinates),
+        None => say!(output, "Hint: no move available"),
+    }
+    State::NextTurn(player.clone(), board.clone())
+}
+
+// The opponent wins, the result is then announced like any other win.
+fn resign(game: &Game, output: &mut dyn Write, player: &Player) -> State {
+    say!(output, "Player {} resigns", game.symbol(player));
+    State::Won(player.next())
+}
+
+// The AI always plays on. A declined offer leaves the turn to be played.
+fn offer_draw(
+    game: &Game,
+    options: &Options,
+    input: &mut dyn InputSource,
+    output: &mut dyn Write,
+    player: &Player,
+    board: &Board,
+) -> State {
+    let opponent = player.next();
+    let accepted = if options.ai.is_some() && opponent != Player::first() {
+        false
+    } else {
+        say!(
+            output,
+            "Player {}, do you accept a draw (y/n) ?",
+            game.symbol(&opponent)
+        );
+        read_yes_no(input)
+    };
+
+    if accepted {
+        State::Draw(DrawReason::Agreed)
+    } else {
+        say!(
+            output,
+            "Player {} declines the draw",
+            game.symbol(&opponent)
+        );
+        State::NextTurn(player.clone(), board.clone())
+    }
+}
+
+fn quit(output: &mut dyn Write) -> State {
+    say!(output, "Game abandoned");
+    State::EndGame
+}
+
+fn draw(output: &mut dyn Write, reason: &DrawReason) -> State {
+    say!(output, "Game finished with a draw, {}", reason);
     State::EndGame
 }
 
-fn won(player: &Player) -> State {
-    println!("Game finished and {:?} won", player);
+fn won(game: &Game, output: &mut dyn Write, player: &Player) -> State {
+    say!(output, "Game finished and {} won", game.symbol(player));
     State::EndGame
 }
 
-fn end_game() -> State {
-    println!("Game finished");
+fn end_game(output: &mut dyn Write) -> State {
+    say!(output, "Game finished");
     State::EndGame
 }
 
-fn turn(game: &Game, state: &State) -> State {
+fn turn(
+    game: &Game,
+    options: &Options,
+    input: &mut dyn InputSource,
+    output: &mut dyn Write,
+    rng: &mut StdRng,
+    state: &State,
+) -> State {
     match state {
-        State::StartGame => start_game(game),
-        State::NextTurn(player, board) => next_turn(game, player, board),
-        State::Draw => draw(),
-        State::Won(player) => won(player),
-        State::EndGame => end_game(),
-    }
-}
-
-fn read_input<A: FromStr>() -> Result<A, String> {
-    let mut input = String::new();
-    io::stdin()
-        .read_line(&mut input)
-        .map_err(|e| format!("Input can't be read: {}", e))
-        .and_then(|_| {
-            input
-                .trim()
-                .parse::<A>()
-                .map_err(|_| "Input can't be parsed".to_string())
+        State::StartGame => State::start_with(game, &options.first),
+        State::NextTurn(player, board) => {
+            next_turn(game, options, input, output, rng, player, board)
+        }
+        State::Draw(reason) => draw(output, reason),
+        State::Won(player) => won(game, output, player),
+        State::EndGame => end_game(output),
+    }
+}
+
+fn parse_yes_no(s: &str) -> Option<bool> {
+    match s.trim().to_lowercase().as_str() {
+        "y" | "yes" => Some(true),
+        "n" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+// Anything but a yes, including unreadable input, counts as a no.
+fn read_yes_no(input: &mut dyn InputSource) -> bool {
+    read_input::<String>(input, None)
+        .ok()
+        .and_then(|line| parse_yes_no(&line))
+        .unwrap_or(false)
+}
+
+// Runs turns from the start until the game ends, handing over every state.
+fn play_game(
+    game: &Game,
+    options: &Options,
+    input: &mut dyn InputSource,
+    output: &mut dyn Write,
+    rng: &mut StdRng,
+    mut record: impl FnMut(&State),
+) {
+    let mut state = State::StartGame;
+    while state != State::EndGame {
+        state = turn(game, options, input, output, rng, &state);
+        record(&state);
+    }
+}
+
+fn read_input<A: FromStr>(input: &mut dyn InputSource, timeout: Option<Duration>) -> io::Result<A> {
+    input.next_line(timeout).and_then(|line| {
+        line.trim()
+            .parse::<A>()
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "Input can't be parsed"))
+    })
+}
+
+struct Arguments {
+    game: Game,
+    best_of: Option<u32>,
+    move_timeout: Option<Duration>,
+    replay: Option<String>,
+    first: Option<Player>,
+    host: Option<u16>,
+    join: Option<String>,
+    watch: Option<String>,
+    seed: Option<u64>,
+    verbose: bool,
+}
+
+// Takes `flag value` out of the arguments, leaving the rest to the game.
+fn take_flag(args: &[String], flag: &str) -> Result<(Option<String>, Vec<String>), String> {
+    match args.iter().position(|arg| arg == flag) {
+        None => Ok((None, args.to_vec())),
+        Some(index) => {
+            let value = args
+                .get(index + 1)
+                .ok_or_else(|| format!("Missing value for {}", flag))?;
+            let rest = args[..index]
+                .iter()
+                .chain(args[index + 2..].iter())
+                .cloned()
+                .collect();
+
+            Ok((Some(value.clone()), rest))
+        }
+    }
+}
+
+// A preset replaces the size flags rather than being adjusted by them.
+fn preset_game(name: &str, args: &[String]) -> Result<Game, String> {
+    if let Some(arg) = args.first() {
+        return Err(format!("Unknown argument {} with --game", arg));
+    }
+    Game::preset(name).ok_or_else(|| {
+        let names = Game::PRESETS
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<&str>>();
+        format!(
+            "Unknown game {}, expected one of {}",
+            name,
+            names.join(", ")
+        )
+    })
+}
+
+// Takes a flag without value out of the arguments.
+fn take_switch(args: &[String], flag: &str) -> (bool, Vec<String>) {
+    let rest = args
+        .iter()
+        .filter(|arg| *arg != flag)
+        .cloned()
+        .collect::<Vec<String>>();
+
+    (rest.len() < args.len(), rest)
+}
+
+fn positive(flag: &str, value: Option<String>) -> Result<Option<u32>, String> {
+    value
+        .map(|value| {
+            value
+                .parse::<u32>()
+                .ok()
+                .filter(|parsed| *parsed > 0)
+                .ok_or_else(|| format!("Invalid value for {}: {}", flag, value))
         })
+        .transpose()
+}
+
+fn port(flag: &str, value: Option<String>) -> Result<Option<u16>, String> {
+    value
+        .map(|value| {
+            value
+                .parse::<u16>()
+                .ok()
+                .filter(|parsed| *parsed > 0)
+                .ok_or_else(|| format!("Invalid value for {}: {}", flag, value))
+        })
+        .transpose()
+}
+
+fn player(flag: &str, value: Option<String>) -> Result<Option<Player>, String> {
+    value
+        .map(|value| {
+            let mut chars = value.chars();
+            match (chars.next().and_then(Player::from_char), chars.next()) {
+                (Some(player), None) => Ok(player),
+                _ => Err(format!("Invalid value for {}: {}", flag, value)),
+            }
+        })
+        .transpose()
+}
+
+fn parse_args(args: &[String]) -> Result<Arguments, String> {
+    let (best_of, args) = take_flag(args, "--best-of")?;
+    let (move_timeout, args) = take_flag(&args, "--move-timeout")?;
+    let (replay, args) = take_flag(&args, "--replay")?;
+    let (first, args) = take_flag(&args, "--first")?;
+    let (host, args) = take_flag(&args, "--host")?;
+    let (join, args) = take_flag(&args, "--join")?;
+    let (watch, args) = take_flag(&args, "--watch")?;
+    let (seed, args) = take_flag(&args, "--seed")?;
+    let (verbose, args) = take_switch(&args, "--verbose");
+    let (preset, args) = take_flag(&args, "--game")?;
+
+    Ok(Arguments {
+        game: match preset {
+            Some(name) => preset_game(&name, &args)?,
+            None => Game::from_args(&args)?,
+        },
+        best_of: positive("--best-of", best_of)?,
+        move_timeout: positive("--move-timeout", move_timeout)?
+            .map(|seconds| Duration::from_secs(u64::from(seconds))),
+        replay,
+        first: player("--first", first)?,
+        host: port("--host", host)?,
+        join,
+        watch,
+        seed: seed
+            .map(|seed| {
+                seed.parse::<u64>()
+                    .map_err(|_| format!("Invalid value for --seed: {}", seed))
+            })
+            .transpose()?,
+        verbose,
+    })
+}
+
+// A recorded game is one `X x,y` move per line, as exported, starting with `first`.
+fn replay_file(
+    game: &Game,
+    path: &str,
+    first: &Player,
+    output: &mut dyn Write,
+) -> Result<(), String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("{} can't be read: {}", path, e))?;
+    let moves = import_moves(&content, first)?;
+    let boards = replay(game, &moves)?;
+
+    for (board, (coordinates, player)) in boards.iter().zip(moves.iter()) {
+        say!(
+            output,
+            "Player {} plays {}",
+            game.symbol(player),
+            coordinates
+        );
+        say!(output, "{}", board);
+        say!(output);
+    }
+    match boards.last().map(|board| board.outcome(game.goal)) {
+        Some(Outcome::Won(player)) => {
+            say!(output, "Game finished and {} won", game.symbol(&player))
+        }
+        Some(Outcome::Draw(reason)) => say!(output, "Game finished with a draw, {}", reason),
+        _ => say!(output, "Game isn't finished"),
+    }
+
+    Ok(())
+}
+
+// Hosting on `port` plays X, joining `address` plays O. Spectators watch the
+// host on the port after it.
+#[cfg(feature = "net")]
+fn play_online(game: &Game, port: Option<u16>, address: Option<String>) -> Result<(), String> {
+    let spectator_port = port
+        .map(|port| {
+            port.checked_add(1)
+                .ok_or_else(|| format!("No port left for spectators after {}", port))
+        })
+        .transpose()?;
+    let stream = match (port, address) {
+        (Some(port), _) => {
+            println!("Waiting for a player on port {}", port);
+            TcpListener::bind(format!("0.0.0.0:{}", port))
+                .and_then(|listener| listener.accept())
+                .map(|(stream, _)| stream)
+        }
+        (None, address) => TcpStream::connect(address.unwrap_or_default()),
+    }
+    .map_err(|e| format!("Connection failed: {}", e))?;
+
+    let mut input = Stdin::new();
+    let mut local = |state: &State| loop {
+        if let State::NextTurn(player, board) = state {
+            println!("Player {}'s turn", game.symbol(player));
+            println!("{}", board);
+        }
+        println!("Where would you like to play ?");
+        let line = read_input::<String>(&mut input, None).map_err(|e| e.to_string())?;
+        match Coordinates::parse(&line, game) {
+            Ok(coordinates) => return Ok(coordinates),
+            Err(e) => println!("Error: {}", e),
+        }
+    };
+    let spectators = match spectator_port {
+        Some(port) => {
+            let listener = TcpListener::bind(format!("0.0.0.0:{}", port))
+                .map_err(|e| format!("Spectators can't connect: {}", e))?;
+            println!("Spectators can watch on port {}", port);
+            Spectators::listen(listener)
+        }
+        None => Spectators::new(),
+    };
+    let mut observe = |board: &Board, events: &[GameEvent]| {
+        if matches!(events.first(), Some(GameEvent::MovePlayed(..))) {
+            spectators.broadcast(board);
+        }
+        for event in events {
+            match event {
+                GameEvent::MovePlayed(coordinates, player) => {
+                    println!("Player {} plays {}", game.symbol(player), coordinates)
+                }
+                GameEvent::IllegalMove(e) => println!("Error: {}", e),
+                GameEvent::Won(player) => println!("Game finished and {} won", game.symbol(player)),
+                GameEvent::Draw(reason) => println!("Game finished with a draw, {}", reason),
+            }
+        }
+    };
+
+    if port.is_some() {
+        net::host(game, stream, &mut local, &mut observe)
+    } else {
+        net::join(game, stream, &mut local, &mut observe)
+    }
+    .map(|_| ())
+}
+
+#[cfg(not(feature = "net"))]
+fn play_online(_: &Game, _: Option<u16>, _: Option<String>) -> Result<(), String> {
+    Err("Playing online needs the net feature".to_string())
+}
+
+#[cfg(feature = "net")]
+fn watch_online(game: &Game, address: &str) -> Result<(), String> {
+    let stream = TcpStream::connect(address).map_err(|e| format!("Connection failed: {}", e))?;
+    net::watch(game, stream, &mut |board| {
+        println!("{}", board);
+        println!();
+    })
+}
+
+#[cfg(not(feature = "net"))]
+fn watch_online(_: &Game, _: &str) -> Result<(), String> {
+    Err("Watching online needs the net feature".to_string())
+}
+
+// Whether another game should follow, reporting the series if there is one.
+fn next_game(
+    game: &Game,
+    input: &mut dyn InputSource,
+    output: &mut dyn Write,
+    series: &Option<Series>,
+    finished: bool,
+) -> bool {
+    match series {
+        Some(_) if !finished => false,
+        Some(series) => match series.is_decided() {
+            Some(player) => {
+                say!(output, "Player {} wins the series", game.symbol(&player));
+                false
+            }
+            None if series.is_over() => {
+                say!(output, "The series ended without a winner");
+                false
+            }
+            None => true,
+        },
+        None => {
+            say!(output, "Play again (y/n) ?");
+            read_yes_no(input)
+        }
+    }
 }
 
 fn main() {
-    let game = Game::TIC_TAC_TOE;
-    let mut state = State::StartGame;
+    let args = env::args().skip(1).collect::<Vec<String>>();
+    let arguments = parse_args(&args).unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        process::exit(1)
+    });
+    let game = arguments.game;
+    let mut output = io::stdout();
+    if let Some(path) = arguments.replay {
+        let first = arguments.first.clone().unwrap_or_else(Player::first);
+        replay_file(&game, &path, &first, &mut output).unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            process::exit(1)
+        });
+        return;
+    }
 
-    while state != State::EndGame {
-        state = turn(&game, &state);
+    if let Some(address) = arguments.watch {
+        watch_online(&game, &address).unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            process::exit(1)
+        });
+        return;
+    }
+    if arguments.host.is_some() || arguments.join.is_some() {
+        play_online(&game, arguments.host, arguments.join).unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            process::exit(1)
+        });
+        return;
+    }
+
+    let mut input = Stdin::new();
+    let first = arguments.first.unwrap_or_else(Player::first);
+    let mut options = Options {
+        ai: choose_difficulty(&mut input, &mut output),
+        move_timeout: arguments.move_timeout,
+        first: first.clone(),
+        colored: io::stdout().is_terminal(),
+        verbose: arguments.verbose,
+    };
+    let seed = arguments.seed.unwrap_or_else(rand::random);
+    say!(
+        output,
+        "Seed: {}, pass --seed {} to play the same games again",
+        seed,
+        seed
+    );
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut scoreboard = Scoreboard::new();
+    let mut series = arguments.best_of.map(Series::new);
+
+    loop {
+        if let Some(series) = &series {
+            let round = series.games_played() + 1;
+            options.first = starting_player(&first, round);
+            say!(
+                output,
+                "Round {}, player {} starts",
+                round,
+                game.symbol(&options.first)
+            );
+        }
+        let mut finished = false;
+        play_game(
+            &game,
+            &options,
+            &mut input,
+            &mut output,
+            &mut rng,
+            |state| {
+                scoreboard.record(state);
+                if let Some(series) = &mut series {
+                    series.record(state);
+                }
+                finished |= matches!(state, State::Won(_) | State::Draw(_));
+            },
+        );
+
+        say!(output, "Score: {}", scoreboard.summary(&game));
+        if !next_game(&game, &mut input, &mut output, &series, finished) {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tic_tac_toe::input::Scripted;
+
+    // Two humans, no time limit and plain output.
+    fn options() -> Options {
+        Options {
+            ai: None,
+            move_timeout: None,
+            first: Player::first(),
+            colored: false,
+            verbose: false,
+        }
+    }
+
+    #[test]
+    fn test_command_parse() {
+        let game = Game::TIC_TAC_TOE;
+
+        assert!(matches!(Command::parse("undo", &game), Ok(Command::Undo)));
+        assert!(matches!(Command::parse("quit", &game), Ok(Command::Quit)));
+        assert!(matches!(Command::parse("Q", &game), Ok(Command::Quit)));
+        assert!(matches!(Command::parse("hint", &game), Ok(Command::Hint)));
+        assert!(matches!(
+            Command::parse("resign", &game),
+            Ok(Command::Resign)
+        ));
+        assert!(matches!(
+            Command::parse("draw?", &game),
+            Ok(Command::OfferDraw)
+        ));
+        assert!(matches!(
+            Command::parse("0,1", &game),
+            Ok(Command::Play(Coordinates { x: 0, y: 1 }))
+        ));
+        assert!(Command::parse("quitting", &game).is_err());
+    }
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_take_flag() {
+        assert_eq!(
+            take_flag(&args(&["--width", "4"]), "--replay"),
+            Ok((None, args(&["--width", "4"])))
+        );
+        assert_eq!(
+            take_flag(
+                &args(&["--width", "4", "--replay", "game.txt", "--goal", "3"]),
+                "--replay"
+            ),
+            Ok((
+                Some("game.txt".to_string()),
+                args(&["--width", "4", "--goal", "3"])
+            ))
+        );
+        assert!(take_flag(&args(&["--replay"]), "--replay").is_err());
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let arguments = parse_args(&args(&["--best-of", "3", "--move-timeout", "10"])).unwrap();
+        assert_eq!(arguments.best_of, Some(3));
+        assert_eq!(arguments.move_timeout, Some(Duration::from_secs(10)));
+        assert_eq!(arguments.replay, None);
+        assert_eq!(arguments.first, None);
+
+        let arguments = parse_args(&args(&["--first", "o"])).unwrap();
+        assert_eq!(arguments.first, Some(Player::O));
+
+        assert!(parse_args(&args(&["--best-of", "0"])).is_err());
+        assert!(parse_args(&args(&["--move-timeout", "soon"])).is_err());
+        assert!(parse_args(&args(&["--first", "OX"])).is_err());
+        assert!(parse_args(&args(&["--first", "Z"])).is_err());
+
+        assert_eq!(
+            parse_args(&args(&["--host", "8080"])).unwrap().host,
+            Some(8080)
+        );
+        assert!(parse_args(&args(&["--host", "0"])).is_err());
+        assert!(parse_args(&args(&["--host", "70000"])).is_err());
+
+        assert_eq!(parse_args(&args(&["--seed", "0"])).unwrap().seed, Some(0));
+        assert!(parse_args(&args(&["--seed", "-1"])).is_err());
+
+        assert!(
+            parse_args(&args(&["--verbose", "--width", "4"]))
+                .unwrap()
+                .verbose
+        );
+        assert!(!parse_args(&args(&[])).unwrap().verbose);
+
+        let arguments = parse_args(&args(&["--game", "connect-four"])).unwrap();
+        assert_eq!(arguments.game, Game::CONNECT_FOUR);
+        assert!(parse_args(&args(&["--game", "chess"])).is_err());
+        assert!(parse_args(&args(&["--game", "gomoku", "--width", "4"])).is_err());
+    }
+
+    #[test]
+    fn test_next_turn_timeout() {
+        let game = Game::TIC_TAC_TOE;
+        let options = Options {
+            move_timeout: Some(Duration::from_millis(1)),
+            ..options()
+        };
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: 0, y: 0 }, &Player::X)
+            .unwrap();
+        let state = next_turn(
+            &game,
+            &options,
+            &mut Scripted::new(&[None]),
+            &mut io::sink(),
+            &mut StdRng::seed_from_u64(42),
+            &Player::O,
+            &board,
+        );
+
+        match state {
+            State::NextTurn(player, next) => {
+                assert_eq!(player, Player::X);
+                assert_eq!(next.count(&Player::X), 1);
+                assert_eq!(next.count(&Player::O), 1);
+                assert_eq!(next.get(&Coordinates { x: 0, y: 0 }), Some(&Player::X));
+            }
+            _ => panic!("the game should go on"),
+        }
+        assert_eq!(board.occupied(), 1);
+    }
+
+    #[test]
+    fn test_next_turn_timeout_late_line() {
+        let game = Game::TIC_TAC_TOE;
+        let options = Options {
+            move_timeout: Some(Duration::from_millis(1)),
+            ..options()
+        };
+        let mut input = Scripted::new(&[None]).late("-1,1").then(&[Some("1,1")]);
+        let mut rng = StdRng::seed_from_u64(42);
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: 0, y: 0 }, &Player::X)
+            .unwrap();
+        let mut turn = |player: &Player, board: &Board| {
+            next_turn(
+                &game,
+                &options,
+                &mut input,
+                &mut io::sink(),
+                &mut rng,
+                player,
+                board,
+            )
+        };
+        let board = match turn(&Player::O, &board) {
+            State::NextTurn(_, board) => board,
+            _ => panic!("the game should go on"),
+        };
+
+        match turn(&Player::X, &board) {
+            State::NextTurn(_, next) => {
+                assert_eq!(next.get(&Coordinates { x: 1, y: 1 }), Some(&Player::X));
+                assert_ne!(next.get(&Coordinates { x: -1, y: 1 }), Some(&Player::X));
+            }
+            _ => panic!("the game should go on"),
+        }
+    }
+
+    #[test]
+    fn test_parse_yes_no() {
+        for yes in ["y", "Y", "yes", "YES", "Yes"].iter() {
+            assert_eq!(parse_yes_no(yes), Some(true));
+        }
+        for no in ["n", "N", "no", "NO", "No"].iter() {
+            assert_eq!(parse_yes_no(no), Some(false));
+        }
+        for garbage in ["", "true", "false", "yep", "nope", "maybe"].iter() {
+            assert_eq!(parse_yes_no(garbage), None);
+        }
+    }
+
+    #[test]
+    fn test_play_game_scripted_win() {
+        let game = Game::TIC_TAC_TOE;
+        let options = options();
+        let mut input = Scripted::new(&[
+            Some("-1,-1"),
+            Some("-1,0"),
+            Some("0,-1"),
+            Some("0,0"),
+            Some("1,-1"),
+        ]);
+        let mut states = vec![];
+        play_game(
+            &game,
+            &options,
+            &mut input,
+            &mut io::sink(),
+            &mut StdRng::seed_from_u64(42),
+            |state| states.push(state.clone()),
+        );
+
+        assert!(states[states.len() - 2] == State::Won(Player::X));
+        assert!(states[states.len() - 1] == State::EndGame);
+    }
+
+    #[test]
+    fn test_play_game_output() {
+        let game = Game::TIC_TAC_TOE;
+        let options = options();
+        let mut input = Scripted::new(&[
+            Some("-1,-1"),
+            Some("-1,0"),
+            Some("0,-1"),
+            Some("0,0"),
+            Some("1,-1"),
+        ]);
+        let mut output = vec![];
+        play_game(
+            &game,
+            &options,
+            &mut input,
+            &mut output,
+            &mut StdRng::seed_from_u64(42),
+            |_| (),
+        );
+        let output = String::from_utf8(output).unwrap();
+        let winning_board = [
+            "  [X]  |  [X]  |  [X]  ",
+            "-------|-------|-------",
+            "   O   |   O   |  1,0  ",
+            "-------|-------|-------",
+            " -1,1  |  0,1  |  1,1  ",
+        ]
+        .join("\n");
+
+        assert!(output.starts_with("Player X's turn\n"));
+        assert!(output.contains(&winning_board));
+        assert!(output.contains("Game finished and X won\n"));
+    }
+
+    #[test]
+    fn test_turn_draw_reason() {
+        let options = options();
+        let mut output = vec![];
+        let state = turn(
+            &Game::TIC_TAC_TOE,
+            &options,
+            &mut Scripted::new(&[]),
+            &mut output,
+            &mut StdRng::seed_from_u64(42),
+            &State::Draw(DrawReason::NoWinningLinePossible),
+        );
+
+        assert!(state == State::EndGame);
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "Game finished with a draw, no line can be completed\n"
+        );
+    }
+
+    #[test]
+    fn test_next_turn_out_of_bounds() {
+        let game = Game::TIC_TAC_TOE;
+        let options = options();
+        let mut output = vec![];
+        let state = next_turn(
+            &game,
+            &options,
+            &mut Scripted::new(&[Some("5,5"), Some("n")]),
+            &mut output,
+            &mut StdRng::seed_from_u64(42),
+            &Player::X,
+            &Board::new(&game),
+        );
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(state == State::EndGame);
+        assert!(output.contains("Error: OutOfBounds\nx goes from -1 to 1 and y from -1 to 1\n"));
+    }
+
+    #[test]
+    fn test_next_turn_resign() {
+        let game = Game::TIC_TAC_TOE;
+        let options = options();
+        let state = next_turn(
+            &game,
+            &options,
+            &mut Scripted::new(&[Some("Resign")]),
+            &mut io::sink(),
+            &mut StdRng::seed_from_u64(42),
+            &Player::X,
+            &Board::new(&game),
+        );
+
+        assert!(state == State::Won(Player::O));
+    }
+
+    #[test]
+    fn test_next_turn_offer_draw() {
+        let game = Game::TIC_TAC_TOE;
+        let options = options();
+        let board = Board::new(&game);
+        let offer = |answer| {
+            next_turn(
+                &game,
+                &options,
+                &mut Scripted::new(&[Some("draw?"), Some(answer)]),
+                &mut io::sink(),
+                &mut StdRng::seed_from_u64(42),
+                &Player::X,
+                &board,
+            )
+        };
+
+        assert!(offer("y") == State::Draw(DrawReason::Agreed));
+        assert!(offer("n") == State::NextTurn(Player::X, board.clone()));
+    }
+
+    #[test]
+    fn test_choose_difficulty_output() {
+        let mut input = Scripted::new(&[Some("impossible"), Some("hard")]);
+        let mut output = vec![];
+        let difficulty = choose_difficulty(&mut input, &mut output);
+        let output = String::from_utf8(output).unwrap();
+
+        assert_eq!(difficulty, Some(Difficulty::Hard));
+        assert!(output.starts_with("Which difficulty would you like to play against"));
+        assert!(output.contains("Error: Difficulty can't be parsed\n"));
+    }
+
+    #[test]
+    fn test_next_game_output() {
+        let game = Game::TIC_TAC_TOE;
+        let mut series = Series::new(1);
+        series.record(&State::Won(Player::O));
+        let mut output = vec![];
+
+        assert!(!next_game(
+            &game,
+            &mut Scripted::new(&[]),
+            &mut output,
+            &Some(series),
+            true
+        ));
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "Player O wins the series\n"
+        );
+
+        let mut output = vec![];
+        assert!(next_game(
+            &game,
+            &mut Scripted::new(&[Some("y")]),
+            &mut output,
+            &None,
+            true
+        ));
+        assert_eq!(String::from_utf8(output).unwrap(), "Play again (y/n) ?\n");
     }
 }
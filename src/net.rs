@@ -0,0 +1,319 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use crate::board::Board;
+use crate::coordinates::Coordinates;
+use crate::event::GameEvent;
+use crate::game::Game;
+use crate::player::Player;
+use crate::state::State;
+
+// One message per line. The client sends its moves as `x,y`. The host answers
+// each of them with `ok` or `error <reason>`, and announces its own moves as
+// `move x,y`.
+struct Connection {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Connection {
+    fn new(stream: TcpStream) -> Result<Connection, String> {
+        let reader = stream
+            .try_clone()
+            .map(BufReader::new)
+            .map_err(|e| format!("Connection failed: {}", e))?;
+
+        Ok(Connection {
+            reader,
+            writer: stream,
+        })
+    }
+
+    fn send(&mut self, message: &str) -> Result<(), String> {
+        writeln!(self.writer, "{}", message).map_err(|e| format!("Connection failed: {}", e))
+    }
+
+    fn receive(&mut self) -> Result<String, String> {
+        let mut line = String::new();
+        match self.reader.read_line(&mut line) {
+            Ok(0) => Err("Connection closed".to_string()),
+            Ok(_) => Ok(line.trim_end().to_string()),
+            Err(e) => Err(format!("Connection failed: {}", e)),
+        }
+    }
+}
+
+fn turn(state: &State) -> Option<(Player, Board)> {
+    match state {
+        State::NextTurn(player, board) => Some((player.clone(), board.clone())),
+        _ => None,
+    }
+}
+
+// `State` drops the board once the game is over, so it's rebuilt from the events.
+fn played(board: Board, events: &[GameEvent]) -> Board {
+    events.iter().fold(board, |board, event| match event {
+        GameEvent::MovePlayed(coordinates, player) => {
+            board.insert(coordinates, player).unwrap_or(board)
+        }
+        _ => board,
+    })
+}
+
+// The host plays first and keeps the authoritative board, refusing the
+// client's illegal moves. `local` asks the host's player for a move and
+// `observe` sees the board after every transition, illegal moves included.
+pub fn host(
+    game: &Game,
+    stream: TcpStream,
+    local: &mut dyn FnMut(&State) -> Result<Coordinates, String>,
+    observe: &mut dyn FnMut(&Board, &[GameEvent]),
+) -> Result<State, String> {
+    let mut connection = Connection::new(stream)?;
+    let mut state = State::start(game);
+
+    while let Some((player, board)) = turn(&state) {
+        let (next, events) = if player == Player::first() {
+            let (next, events) = state.transition(game, &local(&state)?);
+            if let Some(GameEvent::MovePlayed(coordinates, _)) = events.first() {
+                connection.send(&format!("move {}", coordinates))?;
+            }
+            (next, events)
+        } else {
+            let message = connection.receive()?;
+            let (next, events) = match Coordinates::from_str(&message) {
+                Ok(coordinates) => state.transition(game, &coordinates),
+                Err(e) => {
+                    connection.send(&format!("error {}", e))?;
+                    continue;
+                }
+            };
+            match events.first() {
+                Some(GameEvent::IllegalMove(e)) => connection.send(&format!("error {}", e))?,
+                _ => connection.send("ok")?,
+            }
+            (next, events)
+        };
+
+        observe(&played(board, &events), &events);
+        state = next;
+    }
+
+    Ok(state)
+}
+
+// The client mirrors the host's board, so its own illegal moves are caught
+// before being sent.
+pub fn join(
+    game: &Game,
+    stream: TcpStream,
+    local: &mut dyn FnMut(&State) -> Result<Coordinates, String>,
+    observe: &mut dyn FnMut(&Board, &[GameEvent]),
+) -> Result<State, String> {
+    let mut connection = Connection::new(stream)?;
+    let mut state = State::start(game);
+
+    while let Some((player, board)) = turn(&state) {
+        let (next, events) = if player == Player::first() {
+            let message = connection.receive()?;
+            let coordinates = message
+                .strip_prefix("move ")
+                .and_then(|coordinates| Coordinates::from_str(coordinates).ok())
+                .ok_or_else(|| format!("Unexpected message: {}", message))?;
+            let (next, events) = state.transition(game, &coordinates);
+            if let Some(GameEvent::IllegalMove(e)) = events.first() {
+                return Err(format!("The host played an illegal move: {}", e));
+            }
+            (next, events)
+        } else {
+            let (next, events) = state.transition(game, &local(&state)?);
+            if let Some(GameEvent::MovePlayed(coordinates, _)) = events.first() {
+                connection.send(&coordinates.to_string())?;
+                let reply = connection.receive()?;
+                if reply != "ok" {
+                    return Err(format!("The host refused the move: {}", reply));
+                }
+            }
+            (next, events)
+        };
+
+        observe(&played(board, &events), &events);
+        state = next;
+    }
+
+    Ok(state)
+}
+
+// Read-only connections, sent the board as JSON after each move. Nothing they
+// send is ever read.
+#[derive(Clone, Default)]
+pub struct Spectators {
+    streams: Arc<Mutex<Vec<TcpStream>>>,
+}
+
+impl Spectators {
+    pub fn new() -> Spectators {
+        Spectators::default()
+    }
+
+    // Accepts spectators on their own thread for as long as the program runs.
+    pub fn listen(listener: TcpListener) -> Spectators {
+        let spectators = Spectators::new();
+        let accepting = spectators.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                accepting.add(stream);
+            }
+        });
+
+        spectators
+    }
+
+    pub fn add(&self, stream: TcpStream) {
+        if stream.shutdown(Shutdown::Read).is_ok() {
+            self.streams.lock().unwrap().push(stream);
+        }
+    }
+
+    // Spectators who are gone are dropped.
+    pub fn broadcast(&self, board: &Board) {
+        let json = board.to_json();
+        self.streams
+            .lock()
+            .unwrap()
+            .retain_mut(|stream| writeln!(stream, "{}", json).is_ok());
+    }
+}
+
+// Hands over every board broadcast by the host until it hangs up.
+pub fn watch(
+    game: &Game,
+    stream: TcpStream,
+    on_board: &mut dyn FnMut(&Board),
+) -> Result<(), String> {
+    let mut connection = Connection::new(stream)?;
+    loop {
+        match connection.receive() {
+            Ok(json) => on_board(&Board::from_json(&json, game)?),
+            Err(e) if e == "Connection closed" => return Ok(()),
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scripted(moves: &[(i16, i16)]) -> impl FnMut(&State) -> Result<Coordinates, String> + '_ {
+        let mut moves = moves.iter().copied();
+        move |_: &State| {
+            moves
+                .next()
+                .map(|(x, y)| Coordinates { x, y })
+                .ok_or_else(|| "Input can't be read: end of input".to_string())
+        }
+    }
+
+    #[test]
+    fn test_host_join() {
+        let game = Game::TIC_TAC_TOE;
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let hosting = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            host(
+                &game,
+                stream,
+                &mut scripted(&[(-1, -1), (0, -1), (1, -1)]),
+                &mut |_, _| (),
+            )
+        });
+
+        let mut events = vec![];
+        let state = join(
+            &game,
+            TcpStream::connect(address).unwrap(),
+            // The first move is already taken and never reaches the host.
+            &mut scripted(&[(-1, -1), (-1, 0), (0, 0)]),
+            &mut |_, played| events.extend_from_slice(played),
+        );
+
+        assert!(state == Ok(State::Won(Player::X)));
+        assert!(hosting.join().unwrap() == Ok(State::Won(Player::X)));
+        assert_eq!(events.len(), 7);
+        assert_eq!(
+            events[events.len() - 2..],
+            [
+                GameEvent::MovePlayed(Coordinates { x: 1, y: -1 }, Player::X),
+                GameEvent::Won(Player::X),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_host_refuses_illegal_moves() {
+        let game = Game::TIC_TAC_TOE;
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let hosting = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            host(&game, stream, &mut scripted(&[(0, 0)]), &mut |_, _| ())
+        });
+
+        let mut client = Connection::new(TcpStream::connect(address).unwrap()).unwrap();
+        assert_eq!(client.receive(), Ok("move 0,0".to_string()));
+        client.send("0,0").unwrap();
+        assert_eq!(client.receive(), Ok("error AlreadyDefined".to_string()));
+        client.send("nowhere").unwrap();
+        assert_eq!(
+            client.receive(),
+            Ok("error Coordinates can't be parsed: expected x,y".to_string())
+        );
+        client.send("1,1").unwrap();
+        assert_eq!(client.receive(), Ok("ok".to_string()));
+
+        // The host then runs out of scripted moves.
+        assert!(hosting.join().unwrap().is_err());
+    }
+
+    #[test]
+    fn test_spectators() {
+        let game = Game::TIC_TAC_TOE;
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let spectator = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let spectators = Spectators::new();
+        spectators.add(listener.accept().unwrap().0);
+
+        let broadcasting = thread::spawn(move || {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let address = listener.local_addr().unwrap();
+            let hosting = thread::spawn(move || {
+                let (stream, _) = listener.accept().unwrap();
+                host(&game, stream, &mut scripted(&[(0, 0)]), &mut |board, _| {
+                    spectators.broadcast(board)
+                })
+            });
+            let joined = join(
+                &game,
+                TcpStream::connect(address).unwrap(),
+                &mut scripted(&[(1, 1)]),
+                &mut |_, _| (),
+            );
+            // Both sides run out of moves, which closes the spectator as well.
+            assert!(joined.is_err());
+            assert!(hosting.join().unwrap().is_err());
+        });
+
+        let mut boards = vec![];
+        watch(&game, spectator, &mut |board| boards.push(board.clone())).unwrap();
+        broadcasting.join().unwrap();
+
+        assert_eq!(boards.len(), 2);
+        assert_eq!(boards[0].occupied(), 1);
+        assert_eq!(boards[1].get(&Coordinates { x: 1, y: 1 }), Some(&Player::O));
+    }
+}
@@ -0,0 +1,34 @@
+use std::fmt;
+
+use crate::player::Player;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DrawReason {
+    // No move is left, blocked cells and full columns included.
+    BoardFull,
+    // Moves are left but every line is already shared by both players.
+    NoWinningLinePossible,
+    // A player offered a draw and their opponent accepted it.
+    Agreed,
+}
+
+impl fmt::Display for DrawReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DrawReason::BoardFull => write!(f, "the board is full"),
+            DrawReason::NoWinningLinePossible => write!(f, "no line can be completed"),
+            DrawReason::Agreed => write!(f, "both players agreed"),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Outcome {
+    Won(Player),
+    Draw(DrawReason),
+    Ongoing,
+}
@@ -0,0 +1,82 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+pub const COLOR_RESET: &str = "\x1b[0m";
+
+#[derive(PartialEq, Eq, Clone, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Player {
+    X,
+    O,
+}
+
+impl Player {
+    pub fn first() -> Player {
+        Player::X
+    }
+
+    pub fn from_char(c: char) -> Option<Player> {
+        match c.to_ascii_uppercase() {
+            'X' => Some(Player::X),
+            'O' => Some(Player::O),
+            _ => None,
+        }
+    }
+
+    pub fn to_char(&self) -> char {
+        match self {
+            Player::X => 'X',
+            Player::O => 'O',
+        }
+    }
+
+    // ANSI escape code, reset with `COLOR_RESET`.
+    pub fn color(&self) -> &'static str {
+        match self {
+            Player::X => "\x1b[31m",
+            Player::O => "\x1b[34m",
+        }
+    }
+
+    pub fn next(&self) -> Player {
+        match self {
+            Player::O => Player::X,
+            Player::X => Player::O,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next() {
+        assert_eq!(Player::X.next().next(), Player::X);
+        assert_eq!(Player::O.next().next(), Player::O);
+    }
+
+    #[test]
+    fn test_from_char() {
+        let cases = [
+            ('X', Some(Player::X)),
+            ('x', Some(Player::X)),
+            ('O', Some(Player::O)),
+            ('o', Some(Player::O)),
+            ('0', None),
+            ('.', None),
+            ('Y', None),
+            ('❌', None),
+        ];
+
+        for (c, expected) in cases.iter() {
+            assert_eq!(&Player::from_char(*c), expected);
+        }
+    }
+
+    #[test]
+    fn test_to_char() {
+        assert_eq!(Player::from_char(Player::X.to_char()), Some(Player::X));
+        assert_eq!(Player::from_char(Player::O.to_char()), Some(Player::O));
+    }
+}
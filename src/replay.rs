@@ -0,0 +1,173 @@
+use std::str::FromStr;
+
+use crate::board::Board;
+use crate::coordinates::Coordinates;
+use crate::game::Game;
+use crate::player::Player;
+
+// The board after each move, stopping at the first move that can't be played.
+pub fn replay(game: &Game, moves: &[(Coordinates, Player)]) -> Result<Vec<Board>, String> {
+    let mut boards: Vec<Board> = Vec::with_capacity(moves.len());
+    for (index, (coordinates, player)) in moves.iter().enumerate() {
+        let board = boards
+            .last()
+            .cloned()
+            .unwrap_or_else(|| Board::new(game))
+            .insert(coordinates, player)
+            .map_err(|e| {
+                format!(
+                    "Move {} at {} can't be played: {}",
+                    index + 1,
+                    coordinates,
+                    e
+                )
+            })?;
+        boards.push(board);
+    }
+
+    Ok(boards)
+}
+
+// One `X x,y` line per move, in the order they were played.
+pub fn export_moves(history: &[(Coordinates, Player)]) -> String {
+    history
+        .iter()
+        .map(|(coordinates, player)| format!("{} {}\n", player.to_char(), coordinates))
+        .collect()
+}
+
+// Players take turns from `first`, anything else is a corrupted record.
+pub fn import_moves(s: &str, first: &Player) -> Result<Vec<(Coordinates, Player)>, String> {
+    let moves = parse_moves(s)?;
+    let mut expected = first.clone();
+    for (index, (_, player)) in moves.iter().enumerate() {
+        if *player != expected {
+            return Err(format!(
+                "Move {} is played by {}, expected {}",
+                index + 1,
+                player.to_char(),
+                expected.to_char()
+            ));
+        }
+        expected = expected.next();
+    }
+
+    Ok(moves)
+}
+
+fn parse_moves(s: &str) -> Result<Vec<(Coordinates, Player)>, String> {
+    s.lines()
+        .map(str::trim)
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(index, line)| {
+            let mut parts = line.split_whitespace();
+            let player = parts
+                .next()
+                .filter(|player| player.chars().count() == 1)
+                .and_then(|player| player.chars().next())
+                .and_then(Player::from_char);
+            let coordinates = parts
+                .next()
+                .and_then(|coordinates| Coordinates::from_str(coordinates).ok());
+
+            match (player, coordinates, parts.next()) {
+                (Some(player), Some(coordinates), None) => Ok((coordinates, player)),
+                _ => Err(format!("Line {} can't be parsed: {}", index + 1, line)),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replay() {
+        let game = Game::TIC_TAC_TOE;
+        let moves = [
+            (Coordinates { x: -1, y: -1 }, Player::X),
+            (Coordinates { x: 0, y: -1 }, Player::X),
+            (Coordinates { x: 1, y: -1 }, Player::X),
+        ];
+        let boards = replay(&game, &moves).unwrap();
+
+        assert_eq!(boards.len(), 3);
+        assert_eq!(boards[0].occupied(), 1);
+        assert_eq!(boards[1].winner(game.goal), None);
+        assert_eq!(boards[2].winner(game.goal), Some(Player::X));
+    }
+
+    #[test]
+    fn test_replay_illegal() {
+        let game = Game::TIC_TAC_TOE;
+        let moves = [
+            (Coordinates { x: 0, y: 0 }, Player::X),
+            (Coordinates { x: 0, y: 0 }, Player::O),
+            (Coordinates { x: 5, y: 0 }, Player::X),
+        ];
+
+        assert_eq!(
+            replay(&game, &moves).err(),
+            Some("Move 2 at 0,0 can't be played: AlreadyDefined".to_string())
+        );
+    }
+
+    #[test]
+    fn test_replay_empty() {
+        assert!(replay(&Game::TIC_TAC_TOE, &[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_export_moves() {
+        let moves = [
+            (Coordinates { x: 0, y: 0 }, Player::X),
+            (Coordinates { x: -1, y: 1 }, Player::O),
+        ];
+
+        assert_eq!(export_moves(&moves), "X 0,0\nO -1,1\n");
+    }
+
+    #[test]
+    fn test_import_moves() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: 0, y: 0 }, &Player::X)
+            .and_then(|board| board.insert(&Coordinates { x: 1, y: 1 }, &Player::O))
+            .and_then(|board| board.insert(&Coordinates { x: -1, y: 0 }, &Player::X))
+            .unwrap();
+        let exported = export_moves(&board.moves());
+        let imported = import_moves(&exported, &Player::X).unwrap();
+
+        assert_eq!(imported, board.moves());
+        assert!(replay(&game, &imported).unwrap().last() == Some(&board));
+    }
+
+    #[test]
+    fn test_import_moves_invalid() {
+        let first = Player::X;
+        assert!(import_moves("X 0,0\n\nO 1,1", &first).is_ok());
+        assert_eq!(
+            import_moves("X 0,0\nZ 1,1", &first).err(),
+            Some("Line 2 can't be parsed: Z 1,1".to_string())
+        );
+        assert!(import_moves("X", &first).is_err());
+        assert!(import_moves("X 0;0", &first).is_err());
+        assert!(import_moves("X 0,0 O", &first).is_err());
+        assert!(import_moves("XO 0,0", &first).is_err());
+    }
+
+    #[test]
+    fn test_import_moves_alternating() {
+        assert_eq!(
+            import_moves("X 0,0\nX 1,1", &Player::X).err(),
+            Some("Move 2 is played by X, expected O".to_string())
+        );
+        assert_eq!(
+            import_moves("X 0,0", &Player::O).err(),
+            Some("Move 1 is played by X, expected O".to_string())
+        );
+        assert!(import_moves("O 0,0\nX 1,1", &Player::O).is_ok());
+    }
+}
@@ -0,0 +1,111 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::game::Game;
+use crate::state::State;
+
+pub fn save(state: &State, path: &Path) -> io::Result<()> {
+    serde_json::to_string(state)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        .and_then(|json| fs::write(path, json))
+}
+
+pub fn load(path: &Path) -> io::Result<State> {
+    fs::read_to_string(path).and_then(|json| {
+        serde_json::from_str(&json).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    })
+}
+
+// A variant shared as a JSON file, see `Game::from_json`.
+pub fn load_game(path: &Path) -> Result<Game, String> {
+    fs::read_to_string(path)
+        .map_err(|e| format!("{} can't be read: {}", path.display(), e))
+        .and_then(|json| Game::from_json(&json).map_err(|e| format!("{}: {}", path.display(), e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Board;
+    use crate::coordinates::Coordinates;
+    use crate::player::Player;
+    use std::env;
+
+    #[test]
+    fn test_save_load() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: 0, y: 0 }, &Player::X)
+            .unwrap()
+            .insert(&Coordinates { x: -1, y: 1 }, &Player::O)
+            .unwrap();
+        let state = State::NextTurn(Player::X, board);
+        let path = env::temp_dir().join("tic-tac-toe-test-save-load.json");
+
+        save(&state, &path).unwrap();
+        let loaded = load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(loaded == state);
+    }
+
+    #[test]
+    fn test_save_load_blocked() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game)
+            .block(&Coordinates { x: 1, y: 1 })
+            .unwrap()
+            .insert(&Coordinates { x: 0, y: 0 }, &Player::X)
+            .unwrap();
+        let state = State::NextTurn(Player::O, board);
+        let path = env::temp_dir().join("tic-tac-toe-test-save-load-blocked.json");
+
+        save(&state, &path).unwrap();
+        let loaded = load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(loaded == state);
+    }
+
+    #[test]
+    fn test_load_missing() {
+        let path = env::temp_dir().join("tic-tac-toe-test-load-missing.json");
+
+        assert!(load(&path).is_err());
+    }
+
+    #[test]
+    fn test_load_illegal_board() {
+        let path = env::temp_dir().join("tic-tac-toe-test-load-illegal-board.json");
+        let json = [
+            r#"{"NextTurn":["X",{"min_x":-1,"max_x":1,"min_y":-1,"max_y":1,"#,
+            r#""goal":3,"gravity":false,"symbols":["X","O"],"#,
+            r#""moves":[[{"x":5,"y":5},"X"]]}]}"#,
+        ]
+        .concat();
+
+        fs::write(&path, json).unwrap();
+        let error = load(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(error.to_string().starts_with("OutOfBounds"));
+    }
+
+    #[test]
+    fn test_load_game() {
+        let path = env::temp_dir().join("tic-tac-toe-test-load-game.json");
+
+        fs::write(
+            &path,
+            r#"{"min_x": 0, "max_x": 6, "min_y": 0, "max_y": 5, "goal": 4}"#,
+        )
+        .unwrap();
+        let game = load_game(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(game.map(|game| game.goal), Ok(4));
+        assert!(load_game(Path::new("/nonexistent/variant.json")).is_err());
+    }
+}
@@ -0,0 +1,176 @@
+use crate::game::Game;
+use crate::player::Player;
+use crate::state::State;
+
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct Scoreboard {
+    x_wins: u32,
+    o_wins: u32,
+    draws: u32,
+}
+
+impl Scoreboard {
+    pub fn new() -> Scoreboard {
+        Scoreboard::default()
+    }
+
+    // Only finished games count, other states are ignored.
+    pub fn record(&mut self, state: &State) {
+        match state {
+            State::Won(Player::X) => self.x_wins += 1,
+            State::Won(Player::O) => self.o_wins += 1,
+            State::Draw(_) => self.draws += 1,
+            _ => (),
+        }
+    }
+
+    pub fn wins(&self, player: &Player) -> u32 {
+        match player {
+            Player::X => self.x_wins,
+            Player::O => self.o_wins,
+        }
+    }
+
+    pub fn draws(&self) -> u32 {
+        self.draws
+    }
+
+    pub fn games_played(&self) -> u32 {
+        self.x_wins + self.o_wins + self.draws
+    }
+
+    pub fn summary(&self, game: &Game) -> String {
+        format!(
+            "{} {} - {} {}, {} draw(s)",
+            game.symbol(&Player::X),
+            self.x_wins,
+            self.o_wins,
+            game.symbol(&Player::O),
+            self.draws
+        )
+    }
+}
+
+// A best of `best_of` games, won by the first player taking a majority of them.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Series {
+    best_of: u32,
+    scoreboard: Scoreboard,
+}
+
+impl Series {
+    pub fn new(best_of: u32) -> Series {
+        Series {
+            best_of,
+            scoreboard: Scoreboard::new(),
+        }
+    }
+
+    pub fn record(&mut self, state: &State) {
+        self.scoreboard.record(state)
+    }
+
+    pub fn target(&self) -> u32 {
+        self.best_of / 2 + 1
+    }
+
+    pub fn games_played(&self) -> u32 {
+        self.scoreboard.games_played()
+    }
+
+    pub fn is_decided(&self) -> Option<Player> {
+        [Player::X, Player::O]
+            .iter()
+            .find(|player| self.scoreboard.wins(player) >= self.target())
+            .cloned()
+    }
+
+    // Draws can use up every game without anyone reaching the target.
+    pub fn is_over(&self) -> bool {
+        self.is_decided().is_some() || self.games_played() >= self.best_of
+    }
+}
+
+// Rounds count from 1 and swap who moves first, `first` opening the odd ones.
+pub fn starting_player(first: &Player, round: u32) -> Player {
+    if round % 2 == 1 {
+        first.clone()
+    } else {
+        first.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::outcome::DrawReason;
+
+    #[test]
+    fn test_record() {
+        let mut scoreboard = Scoreboard::new();
+        for state in [
+            State::Won(Player::X),
+            State::Draw(DrawReason::BoardFull),
+            State::Won(Player::X),
+            State::Won(Player::O),
+            State::EndGame,
+        ]
+        .iter()
+        {
+            scoreboard.record(state);
+        }
+
+        assert_eq!(scoreboard.wins(&Player::X), 2);
+        assert_eq!(scoreboard.wins(&Player::O), 1);
+        assert_eq!(scoreboard.draws(), 1);
+        assert_eq!(scoreboard.games_played(), 4);
+    }
+
+    #[test]
+    fn test_summary() {
+        let mut scoreboard = Scoreboard::new();
+        scoreboard.record(&State::Won(Player::O));
+        scoreboard.record(&State::Draw(DrawReason::BoardFull));
+
+        assert_eq!(
+            scoreboard.summary(&Game::TIC_TAC_TOE),
+            "X 0 - 1 O, 1 draw(s)"
+        );
+    }
+
+    #[test]
+    fn test_series_best_of_three() {
+        let mut series = Series::new(3);
+        assert_eq!(series.target(), 2);
+
+        series.record(&State::Won(Player::X));
+        assert_eq!(series.is_decided(), None);
+        assert!(!series.is_over());
+
+        series.record(&State::Won(Player::X));
+        assert_eq!(series.is_decided(), Some(Player::X));
+        assert!(series.is_over());
+        assert_eq!(series.games_played(), 2);
+    }
+
+    #[test]
+    fn test_series_draws() {
+        let mut series = Series::new(3);
+        series.record(&State::Won(Player::O));
+        series.record(&State::Draw(DrawReason::BoardFull));
+        assert!(!series.is_over());
+
+        series.record(&State::Draw(DrawReason::BoardFull));
+        assert_eq!(series.is_decided(), None);
+        assert!(series.is_over());
+    }
+
+    #[test]
+    fn test_starting_player() {
+        assert_eq!(starting_player(&Player::X, 1), Player::X);
+        assert_eq!(starting_player(&Player::X, 2), Player::O);
+        assert_eq!(starting_player(&Player::X, 3), Player::X);
+        assert_eq!(starting_player(&Player::O, 1), Player::O);
+        assert_eq!(starting_player(&Player::O, 4), Player::X);
+    }
+}
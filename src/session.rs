@@ -0,0 +1,76 @@
+use crate::board::Board;
+use crate::coordinates::{Coord, Coordinates};
+use crate::game::Game;
+use crate::state::State;
+
+// A game driven one move at a time without any I/O, e.g. from a browser UI.
+// Unlike `State`, it keeps the board once the game is over.
+#[derive(Clone)]
+pub struct Session {
+    game: Game,
+    state: State,
+    board: Board,
+}
+
+impl Session {
+    pub fn new(game: &Game) -> Session {
+        Session {
+            game: *game,
+            state: State::start(game),
+            board: Board::new(game),
+        }
+    }
+
+    pub fn play(&mut self, x: Coord, y: Coord) -> Result<(), String> {
+        let (player, board) = match &self.state {
+            State::NextTurn(player, board) => (player, board),
+            _ => return Err("GameOver".to_string()),
+        };
+        let coordinates = board.resolve(&Coordinates { x, y })?;
+        let next = self.state.advance(&self.game, &coordinates)?;
+
+        self.board = board.insert(&coordinates, player)?;
+        self.state = next;
+        Ok(())
+    }
+
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    pub fn render(&self) -> String {
+        self.board.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::Player;
+
+    #[test]
+    fn test_play() {
+        let mut session = Session::new(&Game::TIC_TAC_TOE);
+        for &(x, y) in [(-1, -1), (-1, 0), (0, -1), (0, 0), (1, -1)].iter() {
+            session.play(x, y).unwrap();
+        }
+
+        assert!(*session.state() == State::Won(Player::X));
+        assert_eq!(session.board().count(&Player::X), 3);
+        assert!(session.render().starts_with("   X   |   X   |   X   "));
+        assert_eq!(session.play(1, 1), Err("GameOver".to_string()));
+    }
+
+    #[test]
+    fn test_play_illegal() {
+        let mut session = Session::new(&Game::TIC_TAC_TOE);
+        session.play(0, 0).unwrap();
+
+        assert_eq!(session.play(0, 0), Err("AlreadyDefined".to_string()));
+        assert_eq!(session.board().occupied(), 1);
+    }
+}
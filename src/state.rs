@@ -0,0 +1,335 @@
+use crate::board::Board;
+use crate::coordinates::Coordinates;
+use crate::event::GameEvent;
+use crate::game::Game;
+use crate::outcome::DrawReason;
+use crate::player::Player;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum State {
+    StartGame,
+    NextTurn(Player, Board),
+    Won(Player),
+    Draw(DrawReason),
+    EndGame,
+}
+
+impl State {
+    pub fn start(game: &Game) -> State {
+        State::start_with(game, &Player::first())
+    }
+
+    pub fn start_with(game: &Game, first: &Player) -> State {
+        State::NextTurn(first.clone(), Board::new(game))
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        match self {
+            State::Won(_) | State::Draw(_) | State::EndGame => true,
+            State::StartGame | State::NextTurn(_, _) => false,
+        }
+    }
+
+    pub fn winner(&self) -> Option<&Player> {
+        match self {
+            State::Won(player) => Some(player),
+            _ => None,
+        }
+    }
+
+    // Plays `coordinates` for the player whose turn it is. An illegal move is an
+    // error, leaving the current state to be played again.
+    pub fn advance(&self, game: &Game, coordinates: &Coordinates) -> Result<State, String> {
+        match self {
+            State::NextTurn(player, board) => {
+                let coordinates = board.resolve(coordinates)?;
+                let board = board.insert(&coordinates, player)?;
+                Ok(State::after(game, player, board, &coordinates))
+            }
+            State::StartGame => Err("NotStarted".to_string()),
+            State::Won(_) | State::Draw(_) | State::EndGame => Err("GameOver".to_string()),
+        }
+    }
+
+    // Like `advance`, for moves claiming to come from `player`, as those arriving
+    // from another process do.
+    pub fn play(
+        &self,
+        game: &Game,
+        player: &Player,
+        coordinates: &Coordinates,
+    ) -> Result<State, String> {
+        match self {
+            State::NextTurn(current, _) if current != player => Err("NotYourTurn".to_string()),
+            _ => self.advance(game, coordinates),
+        }
+    }
+
+    // Like `advance`, reporting what happened as events. Outside of a turn there
+    // is nothing to play, so nothing happens.
+    pub fn transition(&self, game: &Game, coordinates: &Coordinates) -> (State, Vec<GameEvent>) {
+        let (player, board) = match self {
+            State::NextTurn(player, board) => (player, board),
+            _ => return (self.clone(), vec![]),
+        };
+        let played = board.resolve(coordinates).and_then(|coordinates| {
+            board
+                .insert(&coordinates, player)
+                .map(|board| (coordinates, board))
+        });
+
+        match played {
+            Ok((coordinates, board)) => {
+                let next = State::after(game, player, board, &coordinates);
+                let mut events = vec![GameEvent::MovePlayed(coordinates, player.clone())];
+                match &next {
+                    State::Won(winner) => events.push(GameEvent::Won(winner.clone())),
+                    State::Draw(reason) => events.push(GameEvent::Draw(*reason)),
+                    _ => (),
+                }
+                (next, events)
+            }
+            Err(e) => (self.clone(), vec![GameEvent::IllegalMove(e)]),
+        }
+    }
+
+    // The state once `player` has played `coordinates`, giving `board`.
+    fn after(game: &Game, player: &Player, board: Board, coordinates: &Coordinates) -> State {
+        if board.is_winning_move(coordinates, game.goal) {
+            if game.misere {
+                State::Won(player.next())
+            } else {
+                State::Won(player.clone())
+            }
+        } else if let Some(reason) = board.draw_reason(game.goal) {
+            State::Draw(reason)
+        } else {
+            State::NextTurn(player.next(), board)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::MoveError;
+    use std::str::FromStr;
+
+    fn played(game: &Game, moves: &[(i16, i16)]) -> Result<State, String> {
+        moves.iter().try_fold(State::start(game), |state, (x, y)| {
+            state.advance(game, &Coordinates { x: *x, y: *y })
+        })
+    }
+
+    #[test]
+    fn test_start() {
+        let game = Game::TIC_TAC_TOE;
+
+        assert!(State::start(&game) == State::NextTurn(Player::X, Board::new(&game)));
+    }
+
+    #[test]
+    fn test_start_with() {
+        let game = Game::TIC_TAC_TOE;
+        let coordinates = Coordinates { x: 0, y: 0 };
+        let board = Board::new(&game).insert(&coordinates, &Player::O).unwrap();
+        let state = State::start_with(&game, &Player::O).advance(&game, &coordinates);
+
+        assert!(state == Ok(State::NextTurn(Player::X, board)));
+    }
+
+    #[test]
+    fn test_debug() {
+        let board = Board::from_str("X..\n...\n...").unwrap();
+
+        assert_eq!(
+            format!("{:?}", State::NextTurn(Player::O, board)),
+            "NextTurn(O, X..\n...\n...)"
+        );
+    }
+
+    #[test]
+    fn test_is_terminal() {
+        let board = Board::new(&Game::TIC_TAC_TOE);
+
+        assert!(!State::StartGame.is_terminal());
+        assert!(!State::NextTurn(Player::X, board).is_terminal());
+        assert!(State::Won(Player::O).is_terminal());
+        assert!(State::Draw(DrawReason::BoardFull).is_terminal());
+        assert!(State::EndGame.is_terminal());
+    }
+
+    #[test]
+    fn test_winner() {
+        let board = Board::new(&Game::TIC_TAC_TOE);
+
+        assert_eq!(State::StartGame.winner(), None);
+        assert_eq!(State::NextTurn(Player::X, board).winner(), None);
+        assert_eq!(State::Won(Player::O).winner(), Some(&Player::O));
+        assert_eq!(State::Draw(DrawReason::BoardFull).winner(), None);
+        assert_eq!(State::EndGame.winner(), None);
+    }
+
+    #[test]
+    fn test_advance_next_turn() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::new(&game)
+            .insert(&Coordinates { x: 0, y: 0 }, &Player::X)
+            .unwrap();
+
+        assert!(played(&game, &[(0, 0)]) == Ok(State::NextTurn(Player::O, board)));
+    }
+
+    #[test]
+    fn test_advance_won() {
+        let game = Game::TIC_TAC_TOE;
+        let state = played(&game, &[(-1, -1), (-1, 0), (0, -1), (0, 0), (1, -1)]);
+
+        assert!(state == Ok(State::Won(Player::X)));
+    }
+
+    #[test]
+    fn test_advance_misere() {
+        let game = Game {
+            misere: true,
+            ..Game::TIC_TAC_TOE
+        };
+        let state = played(&game, &[(-1, -1), (-1, 0), (0, -1), (0, 0), (1, -1)]);
+
+        assert!(state == Ok(State::Won(Player::O)));
+    }
+
+    #[test]
+    fn test_advance_draw() {
+        let game = Game::TIC_TAC_TOE;
+        let board = Board::from_str("XOX\nXO.\nOXO").unwrap();
+        let state = State::NextTurn(Player::X, board);
+
+        assert!(
+            state.advance(&game, &Coordinates { x: 1, y: 0 })
+                == Ok(State::Draw(DrawReason::BoardFull))
+        );
+    }
+
+    #[test]
+    fn test_advance_goal_larger_than_board() {
+        let game = Game {
+            goal: 10,
+            ..Game::TIC_TAC_TOE
+        };
+
+        assert!(played(&game, &[(0, 0)]) == Ok(State::Draw(DrawReason::NoWinningLinePossible)));
+    }
+
+    #[test]
+    fn test_advance_gravity() {
+        let game = Game::CONNECT_FOUR;
+        let state = played(&game, &[(0, -2)]).unwrap();
+
+        match state {
+            State::NextTurn(_, board) => {
+                assert_eq!(board.get(&Coordinates { x: 0, y: 3 }), Some(&Player::X))
+            }
+            _ => panic!("the game should go on"),
+        }
+    }
+
+    #[test]
+    fn test_advance_illegal_then_retry() {
+        let game = Game::TIC_TAC_TOE;
+        let state = played(&game, &[(0, 0)]).unwrap();
+
+        assert_eq!(
+            state.advance(&game, &Coordinates { x: 0, y: 0 }).err(),
+            Some("AlreadyDefined".to_string())
+        );
+        assert_eq!(
+            state.advance(&game, &Coordinates { x: 3, y: 0 }).err(),
+            Some("OutOfBounds".to_string())
+        );
+
+        let retried = state.advance(&game, &Coordinates { x: 1, y: 0 }).unwrap();
+        match retried {
+            State::NextTurn(player, board) => {
+                assert_eq!(player, Player::X);
+                assert_eq!(board.get(&Coordinates { x: 1, y: 0 }), Some(&Player::O));
+            }
+            _ => panic!("the game should go on"),
+        }
+    }
+
+    #[test]
+    fn test_advance_finished() {
+        let game = Game::TIC_TAC_TOE;
+        let coordinates = Coordinates { x: 0, y: 0 };
+
+        assert!(State::StartGame.advance(&game, &coordinates).is_err());
+        assert!(State::Draw(DrawReason::BoardFull)
+            .advance(&game, &coordinates)
+            .is_err());
+        assert!(State::Won(Player::O).advance(&game, &coordinates).is_err());
+        assert!(State::EndGame.advance(&game, &coordinates).is_err());
+    }
+
+    #[test]
+    fn test_play() {
+        let game = Game::TIC_TAC_TOE;
+        let state = played(&game, &[(0, 0)]).unwrap();
+        let coordinates = Coordinates { x: 1, y: 0 };
+
+        assert_eq!(
+            state.play(&game, &Player::X, &coordinates).err(),
+            Some("NotYourTurn".to_string())
+        );
+        assert!(state.play(&game, &Player::O, &coordinates) == state.advance(&game, &coordinates));
+        assert_eq!(
+            State::Draw(DrawReason::BoardFull)
+                .play(&game, &Player::O, &coordinates)
+                .err(),
+            Some("GameOver".to_string())
+        );
+    }
+
+    #[test]
+    fn test_transition_won() {
+        let game = Game::TIC_TAC_TOE;
+        let state = played(&game, &[(-1, -1), (-1, 0), (0, -1), (0, 0)]).unwrap();
+        let (state, events) = state.transition(&game, &Coordinates { x: 1, y: -1 });
+
+        assert!(state == State::Won(Player::X));
+        assert_eq!(
+            events,
+            vec![
+                GameEvent::MovePlayed(Coordinates { x: 1, y: -1 }, Player::X),
+                GameEvent::Won(Player::X),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_transition_illegal_move() {
+        let game = Game::TIC_TAC_TOE;
+        let state = played(&game, &[(0, 0)]).unwrap();
+        let (next, events) = state.transition(&game, &Coordinates { x: 0, y: 0 });
+
+        assert!(next == state);
+        assert_eq!(
+            events,
+            vec![GameEvent::IllegalMove(MoveError::AlreadyDefined)]
+        );
+    }
+
+    #[test]
+    fn test_transition_gravity() {
+        let game = Game::CONNECT_FOUR;
+        let (_, events) = State::start(&game).transition(&game, &Coordinates { x: 0, y: -3 });
+
+        assert_eq!(
+            events,
+            vec![GameEvent::MovePlayed(Coordinates { x: 0, y: 3 }, Player::X)]
+        );
+    }
+}
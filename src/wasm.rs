@@ -0,0 +1,32 @@
+use wasm_bindgen::prelude::*;
+
+use crate::coordinates::Coord;
+use crate::game::Game;
+use crate::session::Session;
+
+// A `Session` handed over to JavaScript, errors becoming thrown strings.
+#[wasm_bindgen]
+pub struct WasmGame {
+    session: Session,
+}
+
+// Starts one of the presets, e.g. "tic-tac-toe" or "connect-four".
+#[wasm_bindgen]
+pub fn new_game(preset: &str) -> Result<WasmGame, JsValue> {
+    Game::preset(preset)
+        .map(|game| WasmGame {
+            session: Session::new(&game),
+        })
+        .ok_or_else(|| JsValue::from(format!("Unknown game: {}", preset)))
+}
+
+#[wasm_bindgen]
+impl WasmGame {
+    pub fn play(&mut self, x: Coord, y: Coord) -> Result<(), JsValue> {
+        self.session.play(x, y).map_err(JsValue::from)
+    }
+
+    pub fn render(&self) -> String {
+        self.session.render()
+    }
+}